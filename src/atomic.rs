@@ -0,0 +1,141 @@
+use std::{
+    io,
+    fs::{self, File},
+    path::{Path, PathBuf}
+};
+
+/// Writes the file through `write` into a temporary file in the same directory,
+/// which is renamed over the target path only if writing succeeds.
+/// Hence the target is either left untouched or completely replaced,
+/// and never half-written.
+/// If the target is a symbolic link, the file it points to is replaced instead,
+/// and the permissions of an existing target are kept.
+pub fn write_file<P, F>(path: P, write: F) -> io::Result<()>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut File) -> io::Result<()>
+{
+    // write through symbolic links
+    let path = match fs::canonicalize(path.as_ref()) {
+        Ok(path) => path,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => path.as_ref().to_path_buf(),
+        Err(e) => return Err(e)
+    };
+    let temp_path = temp_path(&path);
+
+    // permissions of the existing target
+    let permissions = match fs::metadata(&path) {
+        Ok(metadata) => Some(metadata.permissions()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e)
+    };
+
+    // write and flush the temporary file
+    let result = File::create(&temp_path)
+        .and_then(|mut f| {
+            if let Some(permissions) = permissions {
+                f.set_permissions(permissions)?;
+            }
+            write(&mut f)?;
+            f.sync_all()?;
+            Ok(())
+        });
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    // replace the target in one step
+    fs::rename(&temp_path, &path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })?;
+
+    Ok(())
+}
+
+/// Path of the temporary file next to the target,
+/// which is in the same directory so that renaming does not cross file systems.
+fn temp_path(path: &Path) -> PathBuf {
+    let filename = path.file_name()
+        .map(|filename| filename.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", filename, std::process::id()))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+
+    fn read(path: &Path) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        File::open(path).unwrap().read_to_end(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_write_file() {
+        let filepath = std::env::temp_dir().join("hackpng_test_atomic_write.png");
+        File::create(&filepath).unwrap().write_all(b"original").unwrap();
+
+        write_file(&filepath, |f| f.write_all(b"replaced")).unwrap();
+        assert_eq!(read(&filepath), b"replaced");
+        assert!(!temp_path(&filepath).exists());
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_file_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let filepath = std::env::temp_dir().join("hackpng_test_atomic_permissions.png");
+        let _ = fs::remove_file(&filepath);
+        File::create(&filepath).unwrap().write_all(b"original").unwrap();
+        fs::set_permissions(&filepath, fs::Permissions::from_mode(0o600)).unwrap();
+
+        write_file(&filepath, |f| f.write_all(b"replaced")).unwrap();
+        assert_eq!(read(&filepath), b"replaced");
+        assert_eq!(fs::metadata(&filepath).unwrap().permissions().mode() & 0o777, 0o600);
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_file_through_symlink() {
+        let filepath = std::env::temp_dir().join("hackpng_test_atomic_target.png");
+        let linkpath = std::env::temp_dir().join("hackpng_test_atomic_link.png");
+        let _ = fs::remove_file(&filepath);
+        let _ = fs::remove_file(&linkpath);
+        File::create(&filepath).unwrap().write_all(b"original").unwrap();
+        std::os::unix::fs::symlink(&filepath, &linkpath).unwrap();
+
+        write_file(&linkpath, |f| f.write_all(b"replaced")).unwrap();
+        assert!(fs::symlink_metadata(&linkpath).unwrap().file_type().is_symlink());
+        assert_eq!(read(&filepath), b"replaced");
+
+        std::fs::remove_file(linkpath).unwrap();
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_failed_write_keeps_original() {
+        let filepath = std::env::temp_dir().join("hackpng_test_atomic_failure.png");
+        File::create(&filepath).unwrap().write_all(b"original").unwrap();
+
+        // fail after writing a part of the bytes
+        let result = write_file(&filepath, |f| {
+            f.write_all(b"repl")?;
+            Err(io::Error::other("simulated failure"))
+        });
+        assert!(result.is_err());
+        assert_eq!(read(&filepath), b"original");
+        assert!(!temp_path(&filepath).exists());
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 use crate::{Result, Error};
 use crate::chunk_type::ChunkType;
-use crc32fast;
+use crate::crc;
 
 #[derive(Debug, Clone)]
 pub struct Chunk {
@@ -10,6 +10,12 @@ pub struct Chunk {
 }
 
 impl Chunk {
+
+    /// The maximum length of the chunk data, which is 2^31 - 1 bytes.
+    pub const MAX_LENGTH: usize = (1 << 31) - 1;
+
+    /// The number of bytes in a chunk besides its data, i.e., the length, the chunk type and the CRC.
+    pub const OVERHEAD: usize = 12;
     
     pub fn new(chunk_type: ChunkType, data: Vec<u8>) -> Self {
 
@@ -20,13 +26,38 @@ impl Chunk {
 
     }
 
+    /// Creates a chunk whose data is the UTF-8 bytes of the text.
+    pub fn from_text(chunk_type: ChunkType, text: &str) -> Self {
+        Self::new(chunk_type, text.as_bytes().to_vec())
+    }
+
+    /// Creates a chunk from any data convertible into a byte vector,
+    /// e.g., `Vec<u8>`, `&[u8]` or `String`.
+    pub fn from_bytes_data(chunk_type: ChunkType, data: impl Into<Vec<u8>>) -> Self {
+        Self::new(chunk_type, data.into())
+    }
+
     /// A 4-byte unsigned integer giving the number of bytes in the chunk's data field. 
     /// The length counts only the data field, not itself, the chunk type code, or the CRC. 
     /// Zero is a valid length. 
     /// Although encoders and decoders should treat the length as unsigned, 
     /// its value must not exceed 231 bytes.
+    /// A length that does not fit in 4 bytes saturates at `u32::MAX` instead of panicking,
+    /// see [`Chunk::try_length`] for handling it.
     pub fn length(&self) -> u32 {
-        self.data.len().try_into().unwrap()
+        u32::try_from(self.data.len()).unwrap_or(u32::MAX)
+    }
+
+    /// The length like [`Chunk::length`],
+    /// which fails if the data is longer than [`Chunk::MAX_LENGTH`].
+    pub fn try_length(&self) -> Result<u32> {
+        checked_length(self.data.len())
+    }
+
+    /// The number of bytes in the chunk's data field as a `usize`,
+    /// which is exact unlike [`Chunk::length`] that is meant for serialization.
+    pub fn data_len(&self) -> usize {
+        self.data.len()
     }
 
     /// A 4-byte chunk type code. 
@@ -42,12 +73,30 @@ impl Chunk {
         &self.chunk_type
     }
 
+    /// The chunk type code as a string slice, which is cheaper than `chunk_type().to_string()`
+    /// for comparing type codes since nothing is allocated.
+    /// Returns `None` only if a leniently parsed type code is not valid UTF-8.
+    pub fn type_str(&self) -> Option<&str> {
+        self.chunk_type.as_str()
+    }
+
     /// The data bytes appropriate to the chunk type, if any. 
     /// This field can be of zero length.
     pub fn data(&self) -> &[u8] {
         &self.data
     }
 
+    /// Replaces the data of this chunk.
+    /// The CRC is always computed from the current data, so it stays valid.
+    pub fn set_data(&mut self, data: Vec<u8>) {
+        self.data = data;
+    }
+
+    /// Replaces the type of this chunk while keeping its data.
+    pub fn set_chunk_type(&mut self, chunk_type: ChunkType) {
+        self.chunk_type = chunk_type;
+    }
+
     /// A 4-byte CRC (Cyclic Redundancy Check) calculated on the preceding bytes in the chunk, 
     /// including the chunk type code and chunk data fields, but not including the length field. 
     /// The CRC is always present, even for chunks containing no data. 
@@ -61,8 +110,14 @@ impl Chunk {
             .collect();
 
         // compute CRC
-        crc32fast::hash(&bytes)
+        crc::crc32(&bytes)
+
+    }
 
+    /// Check if the chunk type code is valid and the data length does not exceed the limit.
+    /// Note that the CRC is not checked since it is always recomputed from the data.
+    pub fn is_valid(&self) -> bool {
+        self.chunk_type.is_valid() && self.data.len() <= Self::MAX_LENGTH
     }
 
     pub fn data_as_string(&self) -> Result<String> {
@@ -73,69 +128,150 @@ impl Chunk {
         }
     }
 
+    /// Converts the data to a string, replacing any invalid UTF-8 sequences
+    /// with the replacement character `U+FFFD`.
+    pub fn data_as_string_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.data).into_owned()
+    }
+
+    /// Decodes the data as Latin-1, the encoding of `tEXt` chunks,
+    /// where each byte maps to the Unicode code point of the same value.
+    /// Unlike UTF-8 decoding, this never fails.
+    pub fn data_as_latin1(&self) -> String {
+        latin1_string(&self.data)
+    }
+
+    /// Fraction of the data bytes that are printable ASCII characters,
+    /// including spaces, tabs and line breaks. Empty data has a ratio of zero.
+    pub fn printable_ratio(&self) -> f64 {
+        if self.data.is_empty() {
+            return 0.0;
+        }
+
+        let n_printable = self.data.iter()
+            .filter(|byte| is_printable(**byte) || matches!(byte, b'\n' | b'\r'))
+            .count();
+
+        n_printable as f64 / self.data.len() as f64
+    }
+
+    /// Serializes the chunk, whose length field is only valid if [`Chunk::try_length`] succeeds,
+    /// see [`Chunk::try_as_bytes`] for checking it.
     pub fn as_bytes(&self) -> Vec<u8> {
+        self.as_bytes_with_crc(self.crc())
+    }
+
+    /// Serializes the chunk like [`Chunk::as_bytes`],
+    /// which fails if the data is longer than [`Chunk::MAX_LENGTH`].
+    pub fn try_as_bytes(&self) -> Result<Vec<u8>> {
+        self.try_length()?;
+        Ok(self.as_bytes())
+    }
+
+    /// Serializes the chunk with the given CRC instead of the computed one.
+    /// This is a testing aid for producing deliberately corrupt chunks.
+    pub fn as_bytes_with_crc(&self, crc: u32) -> Vec<u8> {
 
         self.length().to_be_bytes().iter()
             .chain(self.chunk_type.bytes().iter())
             .chain(self.data.iter())
-            .chain(self.crc().to_be_bytes().iter())
+            .chain(crc.to_be_bytes().iter())
             .copied()
             .collect()
 
     }
 
-}
+    /// Parses a chunk from the input bytes.
+    /// If `strict` is set, the chunk type code must consist of ASCII letters,
+    /// otherwise any 4 bytes are accepted as the type code.
+    pub fn from_bytes(value: &[u8], strict: bool) -> Result<Self> {
 
-impl TryFrom<&[u8]> for Chunk {
+        let (chunk, crc) = Self::from_bytes_unchecked_crc(value, strict)?;
 
-    type Error = Error;
+        // check CRC
+        if chunk.crc() == crc {
+            Ok(chunk)
+        } else {
+            log::debug!(
+                "CRC mismatch in chunk {}: found {} but expected {}",
+                chunk.chunk_type(), crc::format_crc(crc), crc::format_crc(chunk.crc())
+            );
+            Err(Box::new(ChunkError::CRCMismatch { expected: chunk.crc(), found: crc }))
+        }
 
-    fn try_from(value: &[u8]) -> std::result::Result<Self, Self::Error> {
+    }
 
-        // a vector of input bytes
-        let mut bytes = value.to_vec();
+    /// Parses a chunk from the input bytes like [`Chunk::from_bytes`] without checking the CRC,
+    /// and returns the chunk together with the CRC stored in the input.
+    pub fn from_bytes_unchecked_crc(value: &[u8], strict: bool) -> Result<(Self, u32)> {
+
+        // the length, chunk type and CRC take 12 bytes
+        if value.len() < 12 {
+            return Err(Box::new(ChunkError::InvalidNumberOfBytes));
+        }
 
         // bytes representing the data length
-        let data_length_bytes: [u8; 4] = bytes.drain(0..4)
-            .collect::<Vec<u8>>()
-            .try_into()
-            .unwrap();
+        let data_length_bytes: [u8; 4] = value[0..4].try_into()?;
 
         // convert to length
         let data_length = u32::from_be_bytes(data_length_bytes);
-        let data_length: usize = data_length.try_into().unwrap();
+        let data_length: usize = data_length.try_into()?;
 
         // bytes representing chunk type
-        let chunk_type_bytes: [u8; 4] = bytes.drain(0..4)
-            .collect::<Vec<u8>>()
-            .try_into()
-            .unwrap();
+        let chunk_type_bytes: [u8; 4] = value[4..8].try_into()?;
 
         // convert to chunk type
-        let chunk_type = ChunkType::try_from(chunk_type_bytes).unwrap();
+        let chunk_type = if strict {
+            ChunkType::try_from(chunk_type_bytes)?
+        } else {
+            ChunkType::from_bytes_lenient(chunk_type_bytes)
+        };
 
-        // message data bytes
-        let data: Vec<u8> = bytes.drain(0..data_length).collect();
+        // the data and the CRC must fit in the remaining bytes,
+        // where only the input of this chunk is sliced instead of copying the rest
+        if data_length > value.len() - 12 {
+            return Err(Box::new(ChunkError::InvalidNumberOfBytes));
+        }
+
+        // message data bytes, which are empty for a zero-length chunk such as IEND
+        let data: Vec<u8> = value[8..8 + data_length].to_vec();
 
         // bytes representing the CRC value
-        let crc_bytes: [u8; 4] = bytes.drain(0..4)
-            .collect::<Vec<u8>>()
-            .try_into()
-            .unwrap();
+        let crc_bytes: [u8; 4] = value[8 + data_length..12 + data_length].try_into()?;
 
         // recover the CRC value
         let crc = u32::from_be_bytes(crc_bytes);
 
-        // create the chunk object
-        let chunk = Chunk::new(chunk_type, data);
+        Ok((Chunk::new(chunk_type, data), crc))
 
-        // check CRC
-        if chunk.crc() == crc {
-            Ok(chunk)
-        } else {
-            Err(Box::new(ChunkError::CRCMismatch))
-        }
+    }
+
+}
 
+/// Converts the number of data bytes into the 4-byte length of a chunk.
+fn checked_length(data_len: usize) -> Result<u32> {
+    if data_len > Chunk::MAX_LENGTH {
+        return Err(Box::new(ChunkError::DataTooLong(data_len)));
+    }
+    Ok(data_len as u32)
+}
+
+/// Checks if the byte is a printable ASCII character, i.e., a graphic character, a space or a tab.
+pub fn is_printable(byte: u8) -> bool {
+    byte.is_ascii_graphic() || byte == b' ' || byte == b'\t'
+}
+
+/// Decodes the Latin-1 bytes into a string.
+pub fn latin1_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| *byte as char).collect()
+}
+
+impl TryFrom<&[u8]> for Chunk {
+
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> std::result::Result<Self, Self::Error> {
+        Self::from_bytes(value, true)
     }
     
 }
@@ -143,7 +279,12 @@ impl TryFrom<&[u8]> for Chunk {
 impl Display for Chunk {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 
-        write!(f, "{}", self.data_as_string().unwrap())
+        // show the data as text if possible,
+        // otherwise only show the number of bytes
+        match std::str::from_utf8(&self.data) {
+            Ok(s) => write!(f, "{}", s),
+            Err(_) => write!(f, "<{} bytes of binary data>", self.data.len())
+        }
 
     }
 }
@@ -151,8 +292,13 @@ impl Display for Chunk {
 #[derive(Debug)]
 pub enum ChunkError {
     StringConvertionFailure,
-    CRCMismatch,
-    InvalidNumberOfBytes
+    /// The CRC computed from the chunk type and data (`expected`)
+    /// differs from the one stored in the input bytes (`found`).
+    CRCMismatch { expected: u32, found: u32 },
+    InvalidNumberOfBytes,
+
+    /// The data has the contained number of bytes, which exceeds [`Chunk::MAX_LENGTH`].
+    DataTooLong(usize)
 }
 
 impl std::error::Error for ChunkError {}
@@ -161,13 +307,21 @@ impl Display for ChunkError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::StringConvertionFailure => {
-                write!(f, "{}", "Failed in converting to string")
+                write!(f, "Failed in converting to string")
             },
-            Self::CRCMismatch => {
-                write!(f, "{}", "The CRC value extracted from the input bytes does not match that of the message data")
+            Self::CRCMismatch { expected, found } => {
+                write!(
+                    f,
+                    "The CRC value extracted from the input bytes ({}) does not match that of the message data ({})",
+                    crc::format_crc(*found),
+                    crc::format_crc(*expected)
+                )
             },
             Self::InvalidNumberOfBytes => {
-                write!(f, "{}", "The number of input bytes is invalid")
+                write!(f, "The number of input bytes is invalid")
+            },
+            Self::DataTooLong(data_len) => {
+                write!(f, "The chunk data has {} bytes, which exceeds the maximum of {} bytes", data_len, Chunk::MAX_LENGTH)
             }
         }
     }
@@ -207,10 +361,55 @@ mod tests {
         assert_eq!(chunk.crc(), 2882656334);
     }
 
+    #[test]
+    fn test_chunk_from_text() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let chunk = Chunk::from_text(chunk_type, "This is where your secret message will be!");
+        assert_eq!(chunk.length(), 42);
+        assert_eq!(chunk.crc(), 2882656334);
+    }
+
+    #[test]
+    fn test_chunk_from_bytes_data() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let chunk = Chunk::from_bytes_data(chunk_type, vec![1, 2, 3]);
+        assert_eq!(chunk.data(), &[1, 2, 3]);
+
+        let chunk = Chunk::from_bytes_data(chunk_type, &b"Message"[..]);
+        assert_eq!(&chunk.data_as_string().unwrap(), "Message");
+    }
+
+    #[test]
+    fn test_as_bytes_with_crc() {
+        let chunk = testing_chunk();
+        let bytes = chunk.as_bytes_with_crc(0xdeadbeef);
+        assert_eq!(bytes[bytes.len() - 4..], 0xdeadbeef_u32.to_be_bytes());
+        assert_eq!(bytes[..bytes.len() - 4], chunk.as_bytes()[..bytes.len() - 4]);
+        assert!(Chunk::try_from(bytes.as_slice()).is_err());
+    }
+
     #[test]
     fn test_chunk_length() {
         let chunk = testing_chunk();
         assert_eq!(chunk.length(), 42);
+        assert_eq!(chunk.try_length().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_checked_length() {
+        assert_eq!(checked_length(0).unwrap(), 0);
+        assert_eq!(checked_length(Chunk::MAX_LENGTH).unwrap(), (1 << 31) - 1);
+
+        // the maximum of the PNG specification is exceeded even if the length fits in 4 bytes
+        let result = checked_length(1 << 31);
+        assert!(result.unwrap_err().to_string().contains("2147483648 bytes"));
+        assert!(checked_length(u32::MAX as usize).is_err());
+
+        #[cfg(target_pointer_width = "64")]
+        {
+            let result = checked_length(u32::MAX as usize + 1);
+            assert!(result.unwrap_err().to_string().contains("4294967296 bytes"));
+        }
     }
 
     #[test]
@@ -219,6 +418,23 @@ mod tests {
         assert_eq!(chunk.chunk_type().to_string(), String::from("RuSt"));
     }
 
+    #[test]
+    fn test_data_len() {
+        let chunk = testing_chunk();
+        assert_eq!(chunk.data_len(), 42);
+        assert_eq!(chunk.data_len(), chunk.length() as usize);
+    }
+
+    #[test]
+    fn test_type_str() {
+        let chunk = testing_chunk();
+        assert_eq!(chunk.type_str(), Some("RuSt"));
+        assert!(chunk.type_str() != Some("ruSt"));
+
+        let chunk = Chunk::new(ChunkType::from_bytes_lenient([0xff, b'u', b'S', b't']), vec![]);
+        assert_eq!(chunk.type_str(), None);
+    }
+
     #[test]
     fn test_chunk_string() {
         let chunk = testing_chunk();
@@ -227,12 +443,94 @@ mod tests {
         assert_eq!(chunk_string, expected_chunk_string);
     }
 
+    #[test]
+    fn test_chunk_string_lossy() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let data = [b"Hello".as_slice(), &[0xff], b"World".as_slice()].concat();
+        let chunk = Chunk::new(chunk_type, data);
+        assert!(chunk.data_as_string().is_err());
+        assert_eq!(chunk.data_as_string_lossy(), "Hello\u{FFFD}World");
+    }
+
+    #[test]
+    fn test_chunk_latin1() {
+        let chunk_type = ChunkType::from_str("tEXt").unwrap();
+        let chunk = Chunk::new(chunk_type, b"Caf\xe9".to_vec());
+        assert!(chunk.data_as_string().is_err());
+        assert_eq!(chunk.data_as_latin1(), "Café");
+    }
+
+    #[test]
+    fn test_chunk_printable_ratio() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        assert_eq!(Chunk::new(chunk_type, b"Hi there\n".to_vec()).printable_ratio(), 1.0);
+        assert_eq!(Chunk::new(chunk_type, vec![b'a', 0x00, 0xff, b'b']).printable_ratio(), 0.5);
+        assert_eq!(Chunk::new(chunk_type, vec![]).printable_ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_zero_length_chunk_round_trip() {
+        let chunk = Chunk::new(ChunkType::from_str("IEND").unwrap(), vec![]);
+        let bytes = chunk.as_bytes();
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(&bytes[0..4], [0, 0, 0, 0]);
+
+        // the CRC covers only the type bytes
+        assert_eq!(chunk.crc(), crc::crc32(b"IEND"));
+        assert_eq!(chunk.crc(), 0xae426082);
+
+        let parsed = Chunk::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(parsed.length(), 0);
+        assert!(parsed.data().is_empty());
+        assert_eq!(parsed.crc(), chunk.crc());
+        assert_eq!(parsed.as_bytes(), bytes);
+
+        // bytes after the chunk are ignored
+        let mut bytes = bytes;
+        bytes.extend(b"rest");
+        assert_eq!(Chunk::try_from(bytes.as_slice()).unwrap().as_bytes(), chunk.as_bytes());
+    }
+
+    #[test]
+    fn test_valid_chunk_is_valid() {
+        let chunk = testing_chunk();
+        assert!(chunk.is_valid());
+    }
+
+    #[test]
+    fn test_invalid_chunk_is_valid() {
+        let chunk_type = ChunkType::from_str("Rust").unwrap();
+        let chunk = Chunk::from_text(chunk_type, "Message");
+        assert!(!chunk.is_valid());
+    }
+
     #[test]
     fn test_chunk_crc() {
         let chunk = testing_chunk();
         assert_eq!(chunk.crc(), 2882656334);
     }
 
+    #[test]
+    fn test_set_data() {
+        let mut chunk = testing_chunk();
+        chunk.set_data(b"New message".to_vec());
+
+        let expected = Chunk::new(ChunkType::from_str("RuSt").unwrap(), b"New message".to_vec());
+        assert_eq!(chunk.length(), 11);
+        assert_eq!(chunk.crc(), expected.crc());
+        assert_eq!(chunk.as_bytes(), expected.as_bytes());
+    }
+
+    #[test]
+    fn test_set_chunk_type() {
+        let mut chunk = testing_chunk();
+        chunk.set_chunk_type(ChunkType::from_str("ruSt").unwrap());
+
+        let expected = Chunk::new(ChunkType::from_str("ruSt").unwrap(), testing_chunk().data().to_vec());
+        assert_eq!(chunk.crc(), expected.crc());
+        assert_ne!(chunk.crc(), testing_chunk().crc());
+    }
+
     #[test]
     fn test_valid_chunk_from_bytes() {
         let data_length: u32 = 42;
@@ -279,6 +577,53 @@ mod tests {
         let chunk = Chunk::try_from(chunk_data.as_ref());
 
         assert!(chunk.is_err());
+
+        let message = chunk.unwrap_err().to_string();
+        assert!(message.contains("0xabd1d84d"));
+        assert!(message.contains("0xabd1d84e"));
+    }
+
+    #[test]
+    fn test_chunk_length_exceeding_bytes() {
+        let data_length: u32 = 1000;
+        let chunk_type = "RuSt".as_bytes();
+        let message_bytes = "This is where your secret message will be!".as_bytes();
+        let crc: u32 = 2882656334;
+
+        let chunk_data: Vec<u8> = data_length
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(message_bytes.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect();
+
+        let chunk = Chunk::try_from(chunk_data.as_ref());
+        assert!(chunk.is_err());
+
+        let chunk = Chunk::try_from(&chunk_data[..6]);
+        assert!(chunk.is_err());
+    }
+
+    #[test]
+    fn test_lenient_chunk_from_bytes() {
+        let chunk_type = ChunkType::from_bytes_lenient([82, 117, 49, 116]);
+        let chunk = Chunk::from_text(chunk_type, "Message");
+        let bytes = chunk.as_bytes();
+
+        assert!(Chunk::try_from(bytes.as_ref()).is_err());
+
+        let chunk = Chunk::from_bytes(bytes.as_ref(), false).unwrap();
+        assert_eq!(chunk.chunk_type().bytes(), [82, 117, 49, 116]);
+        assert_eq!(chunk.as_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_display_binary_chunk() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let chunk = Chunk::new(chunk_type, vec![0xff, 0xfe, 0x00, 0x41]);
+        assert_eq!(format!("{}", chunk), "<4 bytes of binary data>");
     }
 
     #[test]
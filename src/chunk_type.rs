@@ -2,8 +2,18 @@ use std::{
     str::FromStr, 
     fmt::Display
 };
+use rand::Rng;
+
 use crate::Error;
 
+/// A 4-byte chunk type code.
+///
+/// Constructors of `ChunkType`:
+/// - [`ChunkType::from_bytes_validated`], `TryFrom<[u8; 4]>` and `FromStr`
+///   reject bytes which are not ASCII letters.
+/// - [`ChunkType::from_bytes_lenient`] accepts any bytes.
+///
+/// None of them checks the reserved bit, see [`ChunkType::is_valid`].
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct ChunkType {
     bytes: [u8; 4]
@@ -11,11 +21,60 @@ pub struct ChunkType {
 
 impl ChunkType {
 
+    /// Create a chunk type from the given bytes,
+    /// which must all be ASCII letters.
+    /// This is the same as `ChunkType::try_from(bytes)`.
+    pub fn from_bytes_validated(bytes: [u8; 4]) -> crate::Result<Self> {
+
+        if !bytes.iter().all(|byte| byte.is_ascii_alphabetic()) {
+            return Err(Box::new(ChunkTypeError::InvalidCharacter));
+        }
+
+        Ok(ChunkType { bytes })
+    }
+
+    /// Create a random chunk type which is valid, ancillary and private,
+    /// i.e., the first two letters are lowercase and the third is uppercase.
+    /// The safe-to-copy bit is random as well.
+    pub fn random_private_ancillary() -> Self {
+
+        let mut rng = rand::rng();
+        let mut random_letter = || rng.random_range(b'A'..=b'Z');
+
+        let bytes = [
+            random_letter().to_ascii_lowercase(),
+            random_letter().to_ascii_lowercase(),
+            random_letter(),
+            random_letter()
+        ];
+        let mut chunk_type = ChunkType { bytes };
+
+        // randomize the safe-to-copy bit
+        if rand::random::<bool>() {
+            chunk_type.bytes[3] = chunk_type.bytes[3].to_ascii_lowercase();
+        }
+
+        chunk_type
+    }
+
+    /// Create a chunk type from the given bytes without any validation.
+    /// This is meant for reading real-world files whose type codes
+    /// are technically invalid, e.g., containing non-letter bytes.
+    pub fn from_bytes_lenient(bytes: [u8; 4]) -> Self {
+        ChunkType { bytes }
+    }
+
     /// Return the 4 bytes representing the chunk type.
     pub fn bytes(&self) -> [u8; 4] {
         self.bytes
     }
 
+    /// Borrow the chunk type code as a string slice without allocating,
+    /// which is `None` if the bytes are not valid UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.bytes).ok()
+    }
+
     /// Check if the chunk type code is valid.
     pub fn is_valid(&self) -> bool {
         self.bytes.iter().all(|byte| byte.is_ascii_alphabetic()) && self.is_reserved_bit_valid()
@@ -37,6 +96,31 @@ impl ChunkType {
         !Self::is_bit5_zero(self.bytes[3])
     }
 
+    /// Set the ancillary bit so that the chunk is critical or not.
+    pub fn set_critical(&mut self, critical: bool) {
+        Self::set_bit5(&mut self.bytes[0], !critical);
+    }
+
+    /// Set the private bit so that the chunk is public or not.
+    pub fn set_public(&mut self, public: bool) {
+        Self::set_bit5(&mut self.bytes[1], !public);
+    }
+
+    /// Set the safe-to-copy bit.
+    pub fn set_safe_to_copy(&mut self, safe_to_copy: bool) {
+        Self::set_bit5(&mut self.bytes[3], safe_to_copy);
+    }
+
+    /// Set or clear the 5-th bit (value 32) of the given byte,
+    /// i.e., make the letter lowercase or uppercase.
+    fn set_bit5(byte: &mut u8, set: bool) {
+        if set {
+            *byte |= 1 << 5;
+        } else {
+            *byte &= !(1 << 5);
+        }
+    }
+
     /// Check whether the 5-th bit (value 32) of the given byte is zero.
     /// In fact, it is equivalent to the function `is_ascii_uppercase()`
     /// belonging to `u8`.
@@ -52,13 +136,7 @@ impl TryFrom<[u8; 4]> for ChunkType {
     type Error = Error;
 
     fn try_from(value: [u8; 4]) -> Result<Self, Self::Error> {
-
-        let chunk = ChunkType {
-            bytes: value
-        };
-        
-        Ok(chunk)
-
+        Self::from_bytes_validated(value)
     }
 
 }
@@ -71,13 +149,16 @@ impl FromStr for ChunkType {
 
         let bytes = s.as_bytes();
 
+        let unexpected_length = || Box::new(ChunkTypeError::UnexpectedLength {
+            n_chars: s.chars().count(),
+            n_bytes: bytes.len()
+        });
+
         if bytes.len() != 4 {
-            return Err(Box::new(ChunkTypeError::UnexpectedLength(bytes.len())));
+            return Err(unexpected_length());
         } 
         
-        let bytes: [u8; 4] = (&bytes[0..4]).try_into().or_else(|_| {
-            return Err(Box::new(ChunkTypeError::UnexpectedLength(bytes.len())));
-        })?;
+        let bytes: [u8; 4] = (&bytes[0..4]).try_into().map_err(|_| unexpected_length())?;
 
         if !bytes.iter().all(|byte| byte.is_ascii_alphabetic()) {
             return Err(Box::new(ChunkTypeError::InvalidCharacter));
@@ -90,16 +171,22 @@ impl FromStr for ChunkType {
 }
 
 impl Display for ChunkType {
+    /// Writes the type code as a string,
+    /// where the bytes of a type code which is not valid UTF-8 are escaped, e.g., `Ru\xffT`.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s = std::str::from_utf8(&self.bytes).or_else(|_| Err(std::fmt::Error))?;
-        write!(f, "{}", s)
+        match self.as_str() {
+            Some(s) => write!(f, "{}", s),
+            None => write!(f, "{}", self.bytes.escape_ascii())
+        }
     }
 }
 
 #[derive(Debug)]
 pub enum ChunkTypeError {
-    /// The expected length of input string is 4.
-    UnexpectedLength(usize),
+    /// The expected length of input string is 4 bytes.
+    /// Both the number of characters and the number of bytes of the input are kept
+    /// since they differ for non-ASCII input.
+    UnexpectedLength { n_chars: usize, n_bytes: usize },
 
     /// Every character in the input string must be an ASCII letter.
     InvalidCharacter
@@ -110,8 +197,16 @@ impl std::error::Error for ChunkTypeError {}
 impl Display for ChunkTypeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ChunkTypeError::UnexpectedLength(n_bytes) => {
-                write!(f, "The expected length is 4 while the given string has length {}", n_bytes)
+            ChunkTypeError::UnexpectedLength { n_chars, n_bytes } => {
+                write!(
+                    f,
+                    "The expected length is 4 bytes while the given string has {} characters in {} bytes",
+                    n_chars, n_bytes
+                )?;
+                if n_chars != n_bytes {
+                    write!(f, " (non-ASCII characters take more than one byte each, but a chunk type must consist of ASCII letters)")?;
+                }
+                Ok(())
             },
 
             ChunkTypeError::InvalidCharacter => {
@@ -137,6 +232,61 @@ mod tests {
         assert_eq!(expected, actual.bytes());
     }
 
+    #[test]
+    pub fn test_chunk_type_from_invalid_bytes() {
+        let chunk_type = ChunkType::try_from([82, 117, 49, 116]);
+        assert!(chunk_type.is_err());
+    }
+
+    #[test]
+    pub fn test_chunk_type_from_bytes_lenient() {
+        let chunk_type = ChunkType::from_bytes_lenient([82, 117, 49, 116]);
+        assert_eq!(chunk_type.bytes(), [82, 117, 49, 116]);
+        assert!(!chunk_type.is_valid());
+    }
+
+    #[test]
+    pub fn test_chunk_type_from_bytes_validated() {
+        let chunk_type = ChunkType::from_bytes_validated([82, 117, 83, 116]).unwrap();
+        assert_eq!(chunk_type.bytes(), [82, 117, 83, 116]);
+        assert_eq!(chunk_type, ChunkType::try_from([82, 117, 83, 116]).unwrap());
+
+        assert!(ChunkType::from_bytes_validated([82, 117, 49, 116]).is_err());
+
+        // the reserved bit is not checked on construction
+        let chunk_type = ChunkType::from_bytes_validated(*b"Rust").unwrap();
+        assert!(!chunk_type.is_valid());
+    }
+
+    #[test]
+    pub fn test_random_private_ancillary() {
+        for _ in 0..100 {
+            let chunk_type = ChunkType::random_private_ancillary();
+            assert!(chunk_type.is_valid());
+            assert!(!chunk_type.is_critical());
+            assert!(!chunk_type.is_public());
+            assert!(chunk_type.is_reserved_bit_valid());
+            assert!(ChunkType::from_str(&chunk_type.to_string()).is_ok());
+        }
+    }
+
+    #[test]
+    pub fn test_chunk_type_set_bits() {
+        let mut chunk_type = ChunkType::from_str("RUST").unwrap();
+        chunk_type.set_critical(false);
+        chunk_type.set_public(false);
+        chunk_type.set_safe_to_copy(true);
+        assert_eq!(chunk_type.to_string(), "ruSt");
+        assert!(!chunk_type.is_critical());
+        assert!(!chunk_type.is_public());
+        assert!(chunk_type.is_safe_to_copy());
+
+        chunk_type.set_critical(true);
+        chunk_type.set_public(true);
+        chunk_type.set_safe_to_copy(false);
+        assert_eq!(chunk_type.to_string(), "RUST");
+    }
+
     #[test]
     pub fn test_chunk_type_from_str() {
         let expected = ChunkType::try_from([82, 117, 83, 116]).unwrap();
@@ -144,6 +294,26 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    pub fn test_chunk_type_from_str_unexpected_length() {
+        let error = ChunkType::from_str("RuStY").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The expected length is 4 bytes while the given string has 5 characters in 5 bytes"
+        );
+
+        // looks like 4 characters but takes 7 bytes
+        let error = ChunkType::from_str("Ru\u{1F980}t").unwrap_err();
+        match error.downcast_ref::<ChunkTypeError>() {
+            Some(ChunkTypeError::UnexpectedLength { n_chars, n_bytes }) => {
+                assert_eq!((*n_chars, *n_bytes), (4, 7));
+            },
+            _ => panic!("unexpected error: {}", error)
+        }
+        assert!(error.to_string().contains("4 characters in 7 bytes"));
+        assert!(error.to_string().contains("non-ASCII"));
+    }
+
     #[test]
     pub fn test_chunk_type_is_critical() {
         let chunk = ChunkType::from_str("RuSt").unwrap();
@@ -211,6 +381,10 @@ mod tests {
     pub fn test_chunk_type_string() {
         let chunk = ChunkType::from_str("RuSt").unwrap();
         assert_eq!(&chunk.to_string(), "RuSt");
+
+        // bytes which are not valid UTF-8 are escaped
+        let chunk = ChunkType::from_bytes_lenient([b'R', 0xff, b'S', b't']);
+        assert_eq!(&chunk.to_string(), "R\\xffSt");
     }
 
     #[test]
@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::{
+    Result,
+    chunk::Chunk,
+    png::Png
+};
+
+#[derive(Debug, clap::Args)]
+pub struct CapacityArgs {
+
+    /// PNG file to inspect
+    #[arg(value_name = "PNG")]
+    png_filepath: PathBuf,
+
+    /// If set, the resulting file size of embedding a message of this size is also reported
+    #[arg(long, value_name = "BYTES")]
+    message_size: Option<usize>
+
+}
+
+/// Estimate of how much data can be embedded into a PNG.
+#[derive(Debug, PartialEq, Eq)]
+struct Capacity {
+    /// Current size of the PNG in bytes.
+    file_size: usize,
+
+    /// Number of the existing ancillary chunks.
+    ancillary_chunks: usize
+}
+
+impl Capacity {
+
+    fn new(png: &Png) -> Self {
+        Capacity {
+            file_size: png.byte_len(),
+            ancillary_chunks: png.iter_ancillary().count()
+        }
+    }
+
+    /// Number of chunks needed to embed a message of the size,
+    /// where a message that is too large for a single chunk is split.
+    fn chunks_needed(message_size: usize) -> usize {
+        message_size.div_ceil(Chunk::MAX_LENGTH).max(1)
+    }
+
+    /// Size of the PNG in bytes after embedding a message of the size.
+    fn file_size_after(&self, message_size: usize) -> usize {
+        self.file_size + Self::chunks_needed(message_size) * Chunk::OVERHEAD + message_size
+    }
+
+}
+
+pub fn capacity(args: CapacityArgs) -> Result<()> {
+
+    // read the PNG file
+    let png = Png::from_path(&args.png_filepath)?;
+
+    let capacity = Capacity::new(&png);
+
+    println!("File size: {} bytes", capacity.file_size);
+    println!("Ancillary chunks: {}", capacity.ancillary_chunks);
+    println!("Maximum data per chunk: {} bytes", Chunk::MAX_LENGTH);
+    println!("Overhead per chunk: {} bytes", Chunk::OVERHEAD);
+
+    if let Some(message_size) = args.message_size {
+        println!(
+            "{}",
+            format!(
+                "Embedding {} bytes needs {} chunk(s) and grows the file to {} bytes",
+                message_size, Capacity::chunks_needed(message_size), capacity.file_size_after(message_size)
+            ).bright_green()
+        );
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::chunk_type::ChunkType;
+
+    #[test]
+    fn test_capacity() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.insert_chunk(1, Chunk::from_text(ChunkType::from_str("ruSt").unwrap(), "Message")).unwrap();
+        let capacity = Capacity::new(&png);
+        assert_eq!(capacity.file_size, png.as_bytes().len());
+        assert_eq!(capacity.ancillary_chunks, 1);
+
+        // each chunk adds 12 bytes besides the data
+        assert_eq!(capacity.file_size_after(0), capacity.file_size + 12);
+        assert_eq!(capacity.file_size_after(100), capacity.file_size + 112);
+
+        // a message exceeding the maximum chunk length is split
+        assert_eq!(Capacity::chunks_needed(Chunk::MAX_LENGTH), 1);
+        assert_eq!(Capacity::chunks_needed(Chunk::MAX_LENGTH + 1), 2);
+        assert_eq!(capacity.file_size_after(Chunk::MAX_LENGTH + 1), capacity.file_size + Chunk::MAX_LENGTH + 25);
+    }
+}
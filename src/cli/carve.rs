@@ -0,0 +1,110 @@
+use std::{
+    io::Write,
+    fs::File,
+    path::PathBuf
+};
+
+use crate::{
+    Result,
+    chunk::Chunk,
+    png::{self, Png}
+};
+
+#[derive(Debug, clap::Args)]
+pub struct CarveArgs {
+
+    /// File to scan, which does not have to be a valid PNG
+    #[arg(value_name = "PNG")]
+    png_filepath: PathBuf,
+
+    /// If set, each embedded PNG is carved into a file named carved_<OFFSET>.png in this directory
+    #[arg(short, long = "out", value_name = "OUTPUT_DIR")]
+    output_dirpath: Option<PathBuf>
+
+}
+
+pub fn carve(args: CarveArgs) -> Result<()> {
+
+    // read the whole file
+    let bytes = png::read_path(&args.png_filepath)?;
+
+    let offsets = embedded_signature_offsets(&bytes);
+    if offsets.is_empty() {
+        println!("No embedded PNG signature is found");
+    }
+
+    for offset in offsets {
+        let length = embedded_png_len(&bytes[offset..]);
+        println!("Embedded PNG signature at byte offset {} ({} bytes)", offset, length);
+
+        // carve the embedded PNG
+        if let Some(output_dirpath) = &args.output_dirpath {
+            let filepath = output_dirpath.join(format!("carved_{}.png", offset));
+            File::create(&filepath)?
+                .write_all(&bytes[offset..offset + length])?;
+            println!("Carved {}", filepath.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds the byte offsets of the PNG signatures in the bytes besides the leading one.
+fn embedded_signature_offsets(bytes: &[u8]) -> Vec<usize> {
+    bytes.windows(Png::STANDARD_HEADER.len())
+        .enumerate()
+        .skip(1)
+        .filter(|(_, window)| *window == Png::STANDARD_HEADER)
+        .map(|(offset, _)| offset)
+        .collect()
+}
+
+/// Number of bytes of the PNG at the start of the bytes,
+/// i.e., up to the end of its `IEND` chunk,
+/// or all the bytes if its chunks cannot be followed to `IEND`.
+fn embedded_png_len(bytes: &[u8]) -> usize {
+
+    let mut offset = Png::STANDARD_HEADER.len();
+    while offset < bytes.len() {
+        let Ok((chunk, _)) = Chunk::from_bytes_unchecked_crc(&bytes[offset..], false) else {
+            break;
+        };
+        offset += Chunk::OVERHEAD + chunk.data_len();
+        if chunk.type_str() == Some(png::IEND) {
+            return offset;
+        }
+    }
+
+    bytes.len()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::chunk_type::ChunkType;
+
+    #[test]
+    fn test_embedded_signature() {
+        let inner = Png::new_blank(1, 1).unwrap().as_bytes();
+
+        // one PNG hidden in a chunk and another one after IEND
+        let mut outer = Png::new_blank(2, 2).unwrap();
+        outer.insert_chunk(1, Chunk::new(ChunkType::from_str("ruSt").unwrap(), inner.clone())).unwrap();
+        let mut bytes = outer.as_bytes();
+        let trailing_offset = bytes.len();
+        bytes.extend(&inner);
+
+        // the inner PNG starts after the signature, IHDR, and the length and type of the chunk
+        let offsets = embedded_signature_offsets(&bytes);
+        assert_eq!(offsets, vec![8 + 25 + 8, trailing_offset]);
+
+        for offset in offsets {
+            let length = embedded_png_len(&bytes[offset..]);
+            assert_eq!(&bytes[offset..offset + length], inner.as_slice());
+        }
+
+        assert!(embedded_signature_offsets(&inner).is_empty());
+    }
+}
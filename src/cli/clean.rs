@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use crate::{
+    Result,
+    png::Png
+};
+
+#[derive(Debug, clap::Args)]
+pub struct CleanArgs {
+
+    /// PNG file to clean
+    #[arg(value_name = "PNG")]
+    input_png_filepath: PathBuf,
+
+    /// Removes any bytes after the IEND chunk
+    #[arg(long)]
+    trailing: bool,
+
+    /// If set, the cleaned PNG will be saved in this file path
+    #[arg(short, long = "out", value_name = "OUTPUT_FILE")]
+    output_png_filepath: Option<PathBuf>
+
+}
+
+pub fn clean(args: CleanArgs) -> Result<()> {
+
+    // read the PNG file
+    let mut png = Png::from_path(&args.input_png_filepath)?;
+
+    // remove the trailing data
+    if args.trailing {
+        let removed = png.remove_trailing_data();
+        println!("Removed {} bytes after IEND", removed.len());
+    }
+
+    // decide the output file path
+    let output_png_filepath = if let Some(output_png_filepath) = args.output_png_filepath {
+        output_png_filepath
+    } else {
+        args.input_png_filepath
+    };
+
+    // wirte file
+    png.save(output_png_filepath)?;
+
+    Ok(())
+}
@@ -1,14 +1,19 @@
 use std::{
-    io::{Read, Write},
+    io::Write,
     fs::File,
-    path::PathBuf
+    path::PathBuf,
+    fmt::Display
 };
 
 use colored::Colorize;
+use serde_json::json;
 
 use crate::{
     Result,
-    png::Png
+    hex,
+    base64,
+    chunk::{self, ChunkError},
+    png::{self, Png, container, filebox, tag, text}
 };
 
 #[derive(Debug, clap::Args)]
@@ -19,44 +24,472 @@ pub struct DecodeArgs {
     png_filepath: PathBuf,
 
     /// Chunk type corresponding to the messsage chunk
-    chunk_type: String,
+    #[cfg_attr(not(feature = "regex"), arg(required = true))]
+    #[cfg_attr(feature = "regex", arg(required_unless_present = "type_regex"))]
+    chunk_type: Option<String>,
+
+    /// If set, the message is read from the first chunk whose type matches the regular expression
+    #[cfg(feature = "regex")]
+    #[arg(long, value_name = "PATTERN", value_parser = regex::Regex::new, conflicts_with = "chunk_type")]
+    type_regex: Option<regex::Regex>,
 
     /// If set, the decoded message will be written into this file
     #[arg(short = 'o', long = "out", value_name = "OUTPUT_FILE")]
-    output_filepath: Option<PathBuf>
+    output_filepath: Option<PathBuf>,
+
+    /// If set, invalid UTF-8 sequences in the message will be replaced
+    /// instead of failing
+    #[arg(long)]
+    lossy: bool,
+
+    /// Text encoding of the message, where tEXt chunks are encoded in Latin-1
+    #[arg(long, value_enum, default_value_t = TextEncoding::Utf8)]
+    encoding: TextEncoding,
+
+    /// If set, the message will be printed as a hex string of raw bytes
+    #[arg(long)]
+    hex: bool,
+
+    /// If set, the message is treated as a container of named entries,
+    /// which will be listed unless --name is given
+    #[arg(long)]
+    container: bool,
+
+    /// Name of the container entry to extract
+    #[arg(long, requires = "container")]
+    name: Option<String>,
+
+    /// If set, the data of all chunks of the type are concatenated in order
+    /// to recover a message split across multiple chunks
+    #[arg(long, conflicts_with = "select")]
+    join: bool,
+
+    /// If set, the message is read from the chunk at this 0-based index
+    /// among the chunks of the type, instead of the first one
+    #[arg(long, value_name = "INDEX")]
+    select: Option<usize>,
+
+    /// If set, the message is treated as an embedded file,
+    /// which will be recreated with its original file name in this directory
+    #[arg(long = "extract-file", value_name = "OUTPUT_DIR")]
+    extract_dirpath: Option<PathBuf>,
+
+    /// If set, any chunk with a mismatched CRC fails the decoding,
+    /// otherwise only the chunks of the given type must have valid CRCs
+    #[arg(long)]
+    strict: bool,
+
+    /// If set, the message must be tagged by encode --tagged,
+    /// whose tag is checked and removed
+    #[arg(long)]
+    tagged: bool,
+
+    /// If set, the result is printed as a JSON object telling whether the message is found,
+    /// together with the chunk type and the base64-encoded message if so
+    #[arg(long, conflicts_with_all = ["output_filepath", "container", "extract_dirpath"])]
+    json: bool
 
 }
 
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum TextEncoding {
+    Utf8,
+    Latin1
+}
+
 pub fn decode(args: DecodeArgs) -> Result<()> {
 
     // read the PNG file
-    let mut f = File::open(args.png_filepath)?;
-    let mut buffer = Vec::new();
-    f.read_to_end(&mut buffer)?;
+    let buffer = png::read_path(&args.png_filepath)?;
 
     // create a Png object
-    let png = Png::try_from(buffer.as_slice())?;
+    let png = if args.strict {
+        Png::try_from(buffer.as_slice())?
+    } else {
+        tolerant_png(&buffer, |chunk_type| is_message_type(&args, chunk_type))?
+    };
+
+    // decide the chunk type of the message
+    let chunk_type = message_chunk_type(&png, &args)?;
+
+    // language of the message, which is only known for iTXt chunks
+    let mut language_tag = String::new();
+
+    // extract the embedded message
+    let message_bytes = match chunk_type.as_str() {
+        text::ZTXT => {
+
+            // decompress the text transparently
+            png.chunk_by_type(text::ZTXT)
+                .map(text::ZtxtChunk::try_from)
+                .transpose()?
+                .map(|ztxt| ztxt.text().as_bytes().to_vec())
+
+        },
+        text::ITXT => {
+
+            // extract the UTF-8 text and the language tag
+            png.chunk_by_type(text::ITXT)
+                .map(text::ItxtChunk::try_from)
+                .transpose()?
+                .map(|itxt| {
+                    language_tag = itxt.language_tag().to_string();
+                    itxt.text().as_bytes().to_vec()
+                })
+
+        },
+        _ => {
+            if args.join {
+                png::decode_joined_message(&png, &chunk_type)?
+            } else if let Some(index) = args.select {
+                png::decode_selected_message(&png, &chunk_type, index)?
+            } else {
+                png::decode_message(&png, &chunk_type)?
+            }
+        }
+    };
+
+    // check and remove the tag
+    let message_bytes = if args.tagged {
+        message_bytes.map(|message_bytes| tag::decode(&message_bytes)).transpose()?
+    } else {
+        message_bytes
+    };
+
+    // report the result as JSON
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&message_json(&chunk_type, message_bytes.as_deref()))?);
+        if message_bytes.is_none() {
+            return Err(Box::new(DecodeError::MessageNotFound(chunk_type)));
+        }
+        return Ok(());
+    }
+
+    if let Some(message_bytes) = message_bytes {
 
-    // find the chunk containing the message
-    let chunk = png.chunk_by_type(&args.chunk_type);
+        if args.container {
+            return decode_container(&message_bytes, args);
+        }
 
-    // extract the embedded message in the chunk
-    if let Some(chunk) = chunk {
+        if let Some(extract_dirpath) = args.extract_dirpath {
+            let file_box = filebox::decode(&message_bytes)?;
+            let filepath = extract_dirpath.join(&file_box.filename);
+            File::create(&filepath)?
+                .write_all(&file_box.data)?;
+            println!("Extracted {}", filepath.display());
+            return Ok(());
+        }
 
         if let Some(output_filepath) = args.output_filepath {
 
             File::create(output_filepath)?
-                .write(chunk.data())?;
+                .write_all(&message_bytes)?;
 
         } else {
 
-            println!("{}", chunk.data_as_string()?);
+            let message = if args.hex {
+                hex::encode(&message_bytes)
+            } else if let TextEncoding::Latin1 = args.encoding {
+                chunk::latin1_string(&message_bytes)
+            } else if args.lossy {
+                String::from_utf8_lossy(&message_bytes).into_owned()
+            } else {
+                String::from_utf8(message_bytes)
+                    .map_err(|_| Box::new(ChunkError::StringConvertionFailure))?
+            };
+
+            if !language_tag.is_empty() {
+                println!("{}", format!("Language: {}", language_tag).bright_blue());
+            }
+
+            println!("{}", message);
 
         }
         
     } else {
-        println!("{}", "No message is found".bright_yellow())
+        return Err(Box::new(DecodeError::MessageNotFound(chunk_type)));
     }
 
     Ok(())
 }
+
+/// Builds the JSON object reporting the message,
+/// which only tells that it is not found if there is no message.
+fn message_json(chunk_type: &str, message_bytes: Option<&[u8]>) -> serde_json::Value {
+    match message_bytes {
+        Some(message_bytes) => json!({
+            "found": true,
+            "type": chunk_type,
+            "data_base64": base64::encode(message_bytes)
+        }),
+        None => json!({
+            "found": false
+        })
+    }
+}
+
+/// Parses the PNG while tolerating CRC mismatches in the chunks not of the given type,
+/// which are reported as warnings.
+fn tolerant_png<F: Fn(&str) -> bool>(buffer: &[u8], is_message_type: F) -> Result<Png> {
+
+    let (png, crc_mismatches) = Png::from_bytes_tolerant(buffer)?;
+
+    for index in crc_mismatches {
+        let chunk = &png.chunks()[index];
+        if chunk.type_str().is_some_and(&is_message_type) {
+            return Err(Box::new(DecodeError::CrcMismatch(index)));
+        }
+        eprintln!("{}", format!("Warning: the CRC of chunk {} ({}) does not match", index, chunk.chunk_type()).bright_yellow());
+    }
+
+    Ok(png)
+}
+
+/// Checks if the chunk type is the one of the message,
+/// which is given exactly or by --type-regex.
+fn is_message_type(args: &DecodeArgs, chunk_type: &str) -> bool {
+    #[cfg(feature = "regex")]
+    if let Some(type_regex) = &args.type_regex {
+        return type_regex.is_match(chunk_type);
+    }
+    args.chunk_type.as_deref() == Some(chunk_type)
+}
+
+/// Chunk type of the message,
+/// which is the type of the first chunk matching --type-regex if given.
+fn message_chunk_type(png: &Png, args: &DecodeArgs) -> Result<String> {
+    png.chunks()
+        .iter()
+        .filter_map(|chunk| chunk.type_str())
+        .find(|chunk_type| is_message_type(args, chunk_type))
+        .map(str::to_string)
+        .or_else(|| args.chunk_type.clone())
+        .ok_or_else(|| Box::new(DecodeError::MessageNotFound(requested_type(args))).into())
+}
+
+/// The requested chunk type or pattern for reporting.
+fn requested_type(args: &DecodeArgs) -> String {
+    #[cfg(feature = "regex")]
+    if let Some(type_regex) = &args.type_regex {
+        return type_regex.to_string();
+    }
+    args.chunk_type.clone().unwrap_or_default()
+}
+
+fn decode_container(bytes: &[u8], args: DecodeArgs) -> Result<()> {
+
+    // extract the entries of the container
+    let entries = container::decode(bytes)?;
+
+    // list the entries if no name is given
+    let Some(name) = args.name else {
+        for entry in &entries {
+            println!("{}\t{} bytes", entry.name, entry.data.len());
+        }
+        return Ok(());
+    };
+
+    // extract the entry with the given name
+    if let Some(entry) = container::find(&entries, &name) {
+
+        if let Some(output_filepath) = args.output_filepath {
+
+            File::create(output_filepath)?
+                .write_all(&entry.data)?;
+
+        } else {
+
+            println!("{}", String::from_utf8_lossy(&entry.data));
+
+        }
+
+    } else {
+        return Err(Box::new(DecodeError::EntryNotFound(name)));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum DecodeError {
+    /// No chunk of the contained type holds a message.
+    MessageNotFound(String),
+
+    /// The container has no entry of the contained name.
+    EntryNotFound(String),
+
+    /// The message chunk at the contained index has a mismatched CRC.
+    CrcMismatch(usize)
+}
+
+impl std::error::Error for DecodeError {}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MessageNotFound(chunk_type) => {
+                write!(f, "No message of type {} is found{}", chunk_type, png::did_you_mean(chunk_type))
+            },
+            Self::EntryNotFound(name) => {
+                write!(f, "No entry named {} is found", name)
+            },
+            Self::CrcMismatch(index) => {
+                write!(f, "The CRC of the message chunk {} does not match", index)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::str::FromStr;
+    use clap::Parser;
+    use crate::chunk::Chunk;
+    use crate::chunk_type::ChunkType;
+    use crate::cli::Cli;
+
+    #[test]
+    fn test_decode_join() {
+        let chunk_type = ChunkType::from_str("ruSt").unwrap();
+        let png = Png::from_chunks(vec![
+            Chunk::from_text(chunk_type, "Hello, "),
+            Chunk::from_text(chunk_type, "world!"),
+        ]);
+        let png_filepath = std::env::temp_dir().join("hackpng_test_decode_join.png");
+        let output_filepath = std::env::temp_dir().join("hackpng_test_decode_join.txt");
+        File::create(&png_filepath).unwrap().write_all(&png.as_bytes()).unwrap();
+
+        Cli::try_parse_from([
+            "hackpng", "decode", png_filepath.to_str().unwrap(), "ruSt",
+            "--join", "-o", output_filepath.to_str().unwrap()
+        ]).unwrap().run().unwrap();
+
+        let mut message = String::new();
+        File::open(&output_filepath).unwrap().read_to_string(&mut message).unwrap();
+        assert_eq!(message, "Hello, world!");
+
+        std::fs::remove_file(png_filepath).unwrap();
+        std::fs::remove_file(output_filepath).unwrap();
+    }
+
+    #[test]
+    fn test_decode_select() {
+        let chunk_type = ChunkType::from_str("ruSt").unwrap();
+        let png = Png::from_chunks(vec![
+            Chunk::from_text(chunk_type, "first"),
+            Chunk::from_text(chunk_type, "second"),
+            Chunk::from_text(chunk_type, "third"),
+        ]);
+        let png_filepath = std::env::temp_dir().join("hackpng_test_decode_select.png");
+        let output_filepath = std::env::temp_dir().join("hackpng_test_decode_select.txt");
+        File::create(&png_filepath).unwrap().write_all(&png.as_bytes()).unwrap();
+
+        Cli::try_parse_from([
+            "hackpng", "decode", png_filepath.to_str().unwrap(), "ruSt",
+            "--select", "1", "-o", output_filepath.to_str().unwrap()
+        ]).unwrap().run().unwrap();
+
+        let mut message = String::new();
+        File::open(&output_filepath).unwrap().read_to_string(&mut message).unwrap();
+        assert_eq!(message, "second");
+
+        assert!(Cli::try_parse_from([
+            "hackpng", "decode", png_filepath.to_str().unwrap(), "ruSt", "--select", "3"
+        ]).unwrap().run().is_err());
+
+        std::fs::remove_file(png_filepath).unwrap();
+        std::fs::remove_file(output_filepath).unwrap();
+    }
+
+    #[test]
+    fn test_decode_strict() {
+        let png = Png::from_chunks(vec![
+            Chunk::from_text(ChunkType::from_str("miDl").unwrap(), "Other"),
+            Chunk::from_text(ChunkType::from_str("ruSt").unwrap(), "Message"),
+        ]);
+
+        // corrupt the CRC of the chunk which is not the target
+        let mut bytes = png.as_bytes();
+        let (offset, chunk) = png.chunk_offsets()[0];
+        bytes[offset + chunk.as_bytes().len() - 1] ^= 0xff;
+
+        let png_filepath = std::env::temp_dir().join("hackpng_test_decode_strict.png");
+        let output_filepath = std::env::temp_dir().join("hackpng_test_decode_strict.txt");
+        File::create(&png_filepath).unwrap().write_all(&bytes).unwrap();
+
+        Cli::try_parse_from([
+            "hackpng", "decode", png_filepath.to_str().unwrap(), "ruSt",
+            "-o", output_filepath.to_str().unwrap()
+        ]).unwrap().run().unwrap();
+
+        let mut message = String::new();
+        File::open(&output_filepath).unwrap().read_to_string(&mut message).unwrap();
+        assert_eq!(message, "Message");
+
+        assert!(Cli::try_parse_from([
+            "hackpng", "decode", png_filepath.to_str().unwrap(), "ruSt", "--strict"
+        ]).unwrap().run().is_err());
+
+        // the target chunk must always have a valid CRC
+        assert!(Cli::try_parse_from([
+            "hackpng", "decode", png_filepath.to_str().unwrap(), "miDl"
+        ]).unwrap().run().is_err());
+
+        std::fs::remove_file(png_filepath).unwrap();
+        std::fs::remove_file(output_filepath).unwrap();
+    }
+
+    #[test]
+    fn test_message_json() {
+        let report = message_json("ruSt", Some(b"foobar"));
+        assert_eq!(report, json!({"found": true, "type": "ruSt", "data_base64": "Zm9vYmFy"}));
+
+        let report = message_json("ruSt", None);
+        assert_eq!(report, json!({"found": false}));
+    }
+
+    #[test]
+    fn test_decode_untagged() {
+        let png = Png::from_chunks(vec![
+            Chunk::from_text(ChunkType::from_str("ruSt").unwrap(), "Untagged message"),
+        ]);
+        let png_filepath = std::env::temp_dir().join("hackpng_test_decode_untagged.png");
+        png.save(&png_filepath).unwrap();
+
+        let result = Cli::try_parse_from([
+            "hackpng", "decode", png_filepath.to_str().unwrap(), "ruSt", "--tagged"
+        ]).unwrap().run();
+        assert!(result.unwrap_err().to_string().contains("not tagged"));
+
+        std::fs::remove_file(png_filepath).unwrap();
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_decode_type_regex() {
+        let png = Png::from_chunks(vec![
+            Chunk::from_text(ChunkType::from_str("teSt").unwrap(), "Skipped"),
+            Chunk::from_text(ChunkType::from_str("ruSt").unwrap(), "Matched"),
+        ]);
+        let png_filepath = std::env::temp_dir().join("hackpng_test_decode_type_regex.png");
+        let output_filepath = std::env::temp_dir().join("hackpng_test_decode_type_regex.txt");
+        png.save(&png_filepath).unwrap();
+
+        Cli::try_parse_from([
+            "hackpng", "decode", png_filepath.to_str().unwrap(), "--type-regex", "^ru",
+            "-o", output_filepath.to_str().unwrap()
+        ]).unwrap().run().unwrap();
+        assert_eq!(std::fs::read(&output_filepath).unwrap(), b"Matched");
+
+        let result = Cli::try_parse_from([
+            "hackpng", "decode", png_filepath.to_str().unwrap(), "--type-regex", "^zz"
+        ]).unwrap().run();
+        assert_eq!(result.unwrap_err().to_string(), "No message of type ^zz is found");
+
+        std::fs::remove_file(png_filepath).unwrap();
+        std::fs::remove_file(output_filepath).unwrap();
+    }
+}
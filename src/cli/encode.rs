@@ -1,16 +1,21 @@
 use std::{
-    io::{Read, Write},
+    str::FromStr,
+    io::Read,
     fs::File, 
-    path::PathBuf,
-    str::FromStr, 
+    path::{Path, PathBuf},
     fmt::Display
 };
 
+use colored::Colorize;
+
+use super::progress;
+
 use crate::{
     Result,
-    png::Png,
+    hex,
     chunk::Chunk,
-    chunk_type::ChunkType
+    chunk_type::ChunkType,
+    png::{self, Png, container, filebox, tag}
 };
 
 #[derive(Debug, clap::Args)]
@@ -21,49 +26,272 @@ pub struct EncodeArgs {
     input_png_filepath: PathBuf,
 
     /// Chunk type corresponding to the messsage chunk
-    chunk_type: String,
+    #[arg(required_unless_present_any = ["random_type", "chunk_filepath"])]
+    chunk_type: Option<String>,
+
+    /// If set, a random valid private ancillary chunk type is used
+    /// and printed instead of the given chunk type
+    #[arg(long, conflicts_with = "chunk_type")]
+    random_type: bool,
+
+    /// Makes the chunk critical by uppercasing the first letter of the chunk type
+    #[arg(long, requires = "chunk_type", conflicts_with_all = ["ancillary", "random_type", "chunk_filepath"])]
+    critical: bool,
+
+    /// Makes the chunk ancillary by lowercasing the first letter of the chunk type
+    #[arg(long, requires = "chunk_type", conflicts_with_all = ["random_type", "chunk_filepath"])]
+    ancillary: bool,
+
+    /// Makes the chunk public by uppercasing the second letter of the chunk type
+    #[arg(long, requires = "chunk_type", conflicts_with_all = ["private", "random_type", "chunk_filepath"])]
+    public: bool,
+
+    /// Makes the chunk private by lowercasing the second letter of the chunk type
+    #[arg(long, requires = "chunk_type", conflicts_with_all = ["random_type", "chunk_filepath"])]
+    private: bool,
+
+    /// Makes the chunk safe to copy by lowercasing the fourth letter of the chunk type
+    #[arg(long, requires = "chunk_type", conflicts_with_all = ["unsafe_to_copy", "random_type", "chunk_filepath"])]
+    safe_to_copy: bool,
+
+    /// Makes the chunk unsafe to copy by uppercasing the fourth letter of the chunk type
+    #[arg(long, requires = "chunk_type", conflicts_with_all = ["random_type", "chunk_filepath"])]
+    unsafe_to_copy: bool,
+
+    /// If set, no warning is given when the chunk type is a registered standard type
+    #[arg(long)]
+    allow_standard_type: bool,
 
     /// Message to encode
     #[arg(short, long = "msg")]
     message: Option<String>,
 
+    /// If set, the --message option is interpreted as a hex string of raw bytes
+    #[arg(long)]
+    hex: bool,
+
     /// If set, the message will be read from the file,
     /// and the --message option will be ignored
     #[arg(short = 'f', long = "msg-file", value_name = "MESSAGE_FILE")]
     message_filepath: Option<PathBuf>,
 
+    /// If set, the message will be a container of named entries
+    /// given by the --entry options
+    #[arg(long)]
+    container: bool,
+
+    /// Named entry of the container, whose data is read from the file
+    #[arg(long = "entry", value_name = "NAME=FILE", requires = "container")]
+    entries: Vec<String>,
+
+    /// If set, the file will be embedded together with its file name
+    #[arg(long = "embed-file", value_name = "FILE")]
+    embed_filepath: Option<PathBuf>,
+
+    /// If set, the file is read as a single serialized chunk (length, type, data and CRC),
+    /// which is inserted verbatim after checking its CRC
+    #[arg(
+        long = "from-chunk-file",
+        value_name = "CHUNK_FILE",
+        conflicts_with_all = ["chunk_type", "random_type", "message", "message_filepath", "container", "embed_filepath", "tagged"]
+    )]
+    chunk_filepath: Option<PathBuf>,
+
+    /// If set, the data of the first chunk of the type is replaced if it exists,
+    /// otherwise a new chunk is inserted, so that repeated runs do not add duplicates
+    #[arg(long)]
+    upsert: bool,
+
+    /// If set, the message is tagged with a magic, a version and its length,
+    /// so that it can be recognized by decode --tagged
+    #[arg(long)]
+    tagged: bool,
+
     /// Index of the message chunk to insert.
     #[arg(short = 'i', long = "index")]
     chunk_index: Option<usize>,
 
+    /// Maximum size of the message in bytes
+    #[arg(long, value_name = "BYTES")]
+    max_size: Option<usize>,
+
+    /// Maximum total size in bytes of the data of all ancillary chunks,
+    /// including the existing ones and the message chunk
+    #[arg(long, value_name = "BYTES")]
+    max_embedded: Option<usize>,
+
     /// If set, the PNG with encoded message will be saved in this file path
     #[arg(short, long = "out", value_name = "OUTPUT_FILE")]
-    output_png_filepath: Option<PathBuf>
+    output_png_filepath: Option<PathBuf>,
+
+    /// If set, nothing will be written and a summary of the changes is printed instead
+    #[arg(long)]
+    dry_run: bool,
+
+    /// If set, the modification time of the input PNG is kept on the written file
+    #[arg(long)]
+    preserve_mtime: bool,
+
+    /// If set, the progress of reading the message file
+    /// and writing the output file is shown
+    #[arg(long)]
+    progress: bool,
+
+    /// Testing aid: stores the message chunk with this CRC (decimal or 0x-prefixed hex)
+    /// instead of the computed one, producing a deliberately corrupt chunk
+    #[arg(long, value_name = "CRC", value_parser = parse_crc)]
+    force_crc: Option<u32>,
+
+    /// If set and the input PNG does not exist,
+    /// a blank PNG of the given size is created instead
+    #[arg(long = "new", value_name = "WIDTHxHEIGHT", value_parser = parse_size)]
+    new_size: Option<(u32, u32)>
 
 }
 
-pub fn encode(args: EncodeArgs) -> Result<()> {
+impl EncodeArgs {
+
+    /// Creates the arguments for encoding the message file into the PNG file,
+    /// where the result is saved in the output file path.
+    #[cfg(feature = "watch")]
+    pub(super) fn from_message_file(
+        input_png_filepath: PathBuf,
+        chunk_type: String,
+        message_filepath: PathBuf,
+        output_png_filepath: PathBuf
+    ) -> Self {
+        EncodeArgs {
+            input_png_filepath,
+            chunk_type: Some(chunk_type),
+            random_type: false,
+            critical: false,
+            ancillary: false,
+            public: false,
+            private: false,
+            safe_to_copy: false,
+            unsafe_to_copy: false,
+            allow_standard_type: false,
+            upsert: false,
+            tagged: false,
+            message: None,
+            hex: false,
+            message_filepath: Some(message_filepath),
+            container: false,
+            entries: vec![],
+            embed_filepath: None,
+            chunk_filepath: None,
+            chunk_index: None,
+            max_size: None,
+            max_embedded: None,
+            output_png_filepath: Some(output_png_filepath),
+            dry_run: false,
+            preserve_mtime: false,
+            progress: false,
+            force_crc: None,
+            new_size: None
+        }
+    }
+
+    /// Sets the property bits of the chunk type according to the flags,
+    /// leaving the bits without a flag as they are.
+    fn with_property_bits(&self, chunk_type: &str) -> Result<String> {
 
-    // read the PNG file
-    let mut f = File::open(&args.input_png_filepath)?;
-    let mut buffer = Vec::new();
-    f.read_to_end(&mut buffer)?;
+        let mut chunk_type = ChunkType::from_str(chunk_type)?;
+
+        if self.critical || self.ancillary {
+            chunk_type.set_critical(self.critical);
+        }
+        if self.public || self.private {
+            chunk_type.set_public(self.public);
+        }
+        if self.safe_to_copy || self.unsafe_to_copy {
+            chunk_type.set_safe_to_copy(self.safe_to_copy);
+        }
+
+        Ok(chunk_type.to_string())
+    }
+
+}
+
+pub fn encode(args: EncodeArgs) -> Result<()> {
 
     // create a Png object
-    let mut png = Png::try_from(buffer.as_slice())?;
+    let (mut png, buffer) = match args.new_size {
+        Some((width, height)) if !args.input_png_filepath.exists() => {
+
+            // create a blank PNG
+            let png = Png::new_blank(width, height)?;
+            let buffer = png.as_bytes();
+            (png, buffer)
+
+        },
+        _ => {
+
+            // read the PNG file
+            let buffer = png::read_path(&args.input_png_filepath)?;
+
+            (Png::try_from(buffer.as_slice())?, buffer)
+
+        }
+    };
+
+    // modification time of the input PNG to restore
+    let mtime = if args.preserve_mtime && args.input_png_filepath.exists() {
+        Some(std::fs::metadata(&args.input_png_filepath)?.modified()?)
+    } else {
+        None
+    };
+
+    // read the pre-built chunk
+    let chunk_from_file = match &args.chunk_filepath {
+        Some(chunk_filepath) => Some(read_chunk_file(chunk_filepath)?),
+        None => None
+    };
 
     // get message bytes
-    let message_bytes: Vec<u8> = if let Some(message) = args.message {
+    let message_bytes: Vec<u8> = if let Some(chunk) = &chunk_from_file {
+
+        // the data of the pre-built chunk
+        chunk.data().to_vec()
+
+    } else if args.container {
+
+        // build the container from the entries
+        let mut entries: Vec<container::Entry> = vec![];
+        for entry in &args.entries {
+            let (name, filepath) = entry.split_once('=')
+                .ok_or_else(|| Box::new(EncodeError::InvalidEntry(entry.clone())))?;
+            let mut bytes: Vec<u8> = vec![];
+            File::open(filepath)?.read_to_end(&mut bytes)?;
+            entries.push(container::Entry::new(name, bytes));
+        }
+
+        container::encode(&entries)
 
-        // the message is simply a string
-        message.as_bytes().try_into()?
+    } else if let Some(embed_filepath) = &args.embed_filepath {
 
-    } else if let Some(message_filepath) = args.message_filepath {
+        // read the file and keep its file name
+        let bytes = progress::read_file(embed_filepath, args.progress)?;
+        filebox::encode(&filebox::FileBox::new(embed_filepath, bytes)?)?
+
+    } else if let Some(message) = &args.message {
+
+        if args.hex {
+
+            // the message is a hex string of raw bytes
+            hex::decode(message)?
+
+        } else {
+
+            // the message is simply a string
+            message.as_bytes().into()
+
+        }
+
+    } else if let Some(message_filepath) = &args.message_filepath {
 
         // read message from file
-        let mut bytes: Vec<u8> = vec![];
-        File::open(message_filepath)?.read_to_end(&mut bytes)?;
-        bytes
+        progress::read_file(message_filepath, args.progress)?
 
     } else {
 
@@ -72,22 +300,61 @@ pub fn encode(args: EncodeArgs) -> Result<()> {
 
     };
 
-    // create the chunk from the given chunk type and message
-    let chunk = Chunk::new(
-        ChunkType::from_str(&args.chunk_type)?,
+    // guard against unexpectedly large messages
+    check_message_size(message_bytes.len(), buffer.len(), args.max_size)?;
+
+    // tag the message
+    let message_bytes = if args.tagged {
+        tag::encode(&message_bytes)?
+    } else {
         message_bytes
-    );
+    };
+
+    // size of the message for the dry run
+    let message_size = message_bytes.len();
+
+    // decide the chunk type
+    let chunk_type = match (chunk_from_file, &args.chunk_type) {
+        (Some(chunk), _) => chunk.chunk_type().to_string(),
+        (None, Some(chunk_type)) => args.with_property_bits(chunk_type)?,
+        (None, None) => {
+            let chunk_type = ChunkType::random_private_ancillary().to_string();
+            println!("Chunk type: {}", chunk_type.bright_green());
+            chunk_type
+        }
+    };
+
+    // viewers may misinterpret the message as a standard chunk
+    if !args.allow_standard_type {
+        if let Some(warning) = standard_type_warning(&chunk_type) {
+            eprintln!("{}", warning.bright_yellow());
+        }
+    }
 
     // encode the message into PNG
-    match args.chunk_index {
-        Some(index) => {
-            png.insert_chunk(index, chunk)
-        },
-        None => {
-            png.append_chunk(chunk);
+    let (message_index, replaced) = if args.upsert {
+        png::upsert_message(&mut png, &chunk_type, message_bytes, args.chunk_index)?
+    } else {
+        png::encode_message(&mut png, &chunk_type, message_bytes, args.chunk_index)?;
+        (args.chunk_index.unwrap_or(png.len() - 1), false)
+    };
+
+    // enforce the budget of the embedded data
+    if let Some(max_embedded) = args.max_embedded {
+        let embedded_size = png.data_sizes().ancillary;
+        if embedded_size > max_embedded {
+            return Err(Box::new(EncodeError::EmbeddedTooLarge { size: embedded_size, max_size: max_embedded }));
         }
     }
 
+    // only print the summary in a dry run
+    if args.dry_run {
+        let action = if replaced { "replaced" } else { "added" };
+        println!("Chunk {} ({} bytes) would be {} at index {}", chunk_type, message_size, action, message_index);
+        println!("File size would change from {} to {} bytes", buffer.len(), png.as_bytes().len());
+        return Ok(());
+    }
+
     // decide the output file path
     let output_png_filepath = if let Some(output_png_filepath) = args.output_png_filepath {
         output_png_filepath
@@ -95,16 +362,167 @@ pub fn encode(args: EncodeArgs) -> Result<()> {
         args.input_png_filepath
     };
 
+    // make sure that a valid PNG is written unless it is corrupted on purpose
+    let png_bytes = if let Some(crc) = args.force_crc {
+        bytes_with_forced_crc(&png, message_index, crc)?
+    } else {
+        verified_bytes(&png)?
+    };
+
     // wirte file
-    let mut f = File::create(output_png_filepath)?;
-    f.write(png.as_bytes().as_slice())?;
+    progress::write_file(&output_png_filepath, png_bytes.as_slice(), args.progress)?;
+
+    // restore the modification time
+    if let Some(mtime) = mtime {
+        File::options()
+            .write(true)
+            .open(&output_png_filepath)?
+            .set_modified(mtime)?;
+    }
+
+    Ok(())
+}
+
+/// Reads the file as a single serialized chunk, whose CRC must match.
+fn read_chunk_file(chunk_filepath: &Path) -> Result<Chunk> {
+
+    let bytes = png::read_path(chunk_filepath)?;
+    let chunk = Chunk::try_from(bytes.as_slice())?;
+
+    // the file must contain nothing else
+    let n_extra_bytes = bytes.len() - (Chunk::OVERHEAD + chunk.data_len());
+    if n_extra_bytes > 0 {
+        return Err(Box::new(EncodeError::ExtraChunkBytes(n_extra_bytes)));
+    }
+
+    Ok(chunk)
+}
+
+/// Serializes the PNG and checks that the bytes parse back into the same PNG,
+/// so that a corrupt file is never written.
+fn verified_bytes(png: &Png) -> Result<Vec<u8>> {
+
+    let bytes = png.try_as_bytes()?;
+
+    // parse the bytes again
+    let reparsed = Png::try_from(bytes.as_slice())
+        .map_err(|e| Box::new(EncodeError::InvalidResult(e.to_string())))?;
+
+    // the chunks must be preserved
+    if reparsed.len() != png.len() || reparsed.as_bytes() != bytes {
+        return Err(Box::new(EncodeError::InvalidResult("the chunks are not preserved".to_string())));
+    }
+
+    Ok(bytes)
+}
+
+/// Serializes the PNG, where the chunk at `index` is stored with the given CRC.
+/// Only the CRC is corrupted on purpose, so the chunk lengths must still be valid.
+fn bytes_with_forced_crc(png: &Png, index: usize, crc: u32) -> Result<Vec<u8>> {
+
+    let mut bytes: Vec<u8> = png.header().to_vec();
+    for (i, chunk) in png.chunks().iter().enumerate() {
+        chunk.try_length()?;
+        if i == index {
+            bytes.extend(chunk.as_bytes_with_crc(crc));
+        } else {
+            bytes.extend(chunk.as_bytes());
+        }
+    }
+    bytes.extend(png.trailing_data());
+
+    Ok(bytes)
+}
+
+/// Parses the CRC as a decimal number or a `0x`-prefixed hex number.
+fn parse_crc(s: &str) -> std::result::Result<u32, String> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => s.parse()
+    }.map_err(|_| format!("invalid CRC {}", s))
+}
+
+/// Parses the image size in the form of `<WIDTH>x<HEIGHT>`.
+fn parse_size(s: &str) -> std::result::Result<(u32, u32), String> {
+
+    let (width, height) = s.split_once('x')
+        .ok_or_else(|| format!("expected <WIDTH>x<HEIGHT> but got {}", s))?;
+
+    let width: u32 = width.parse().map_err(|_| format!("invalid width {}", width))?;
+    let height: u32 = height.parse().map_err(|_| format!("invalid height {}", height))?;
+
+    Ok((width, height))
+}
+
+/// Returns a warning if the chunk type collides with a registered standard type,
+/// suggesting the private and ancillary variant with lowercase first and second letters instead.
+/// Since a few registered types, e.g., `fdAT`, are already private and ancillary,
+/// the safe-to-copy letter is flipped as well if the variant is still registered.
+fn standard_type_warning(chunk_type: &str) -> Option<String> {
+
+    if !png::is_standard_chunk_type(chunk_type) {
+        return None;
+    }
+
+    // standard types always consist of letters
+    let mut suggestion = ChunkType::from_str(chunk_type).ok()?;
+    suggestion.set_critical(false);
+    suggestion.set_public(false);
+    if png::is_standard_chunk_type(&suggestion.to_string()) {
+        suggestion.set_safe_to_copy(!suggestion.is_safe_to_copy());
+    }
+    while png::is_standard_chunk_type(&suggestion.to_string()) {
+        suggestion = ChunkType::random_private_ancillary();
+    }
+
+    Some(format!(
+        "Warning: {} is a standard chunk type which viewers may misinterpret, consider the private type {} instead (or use --allow-standard-type)",
+        chunk_type, suggestion
+    ))
+}
+
+/// Messages larger than this size (16 MiB) trigger a warning.
+const WARNING_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Checks the message size against the limit,
+/// and warns if the message is suspiciously large.
+fn check_message_size(message_size: usize, png_size: usize, max_size: Option<usize>) -> Result<()> {
+
+    if let Some(max_size) = max_size {
+        if message_size > max_size {
+            return Err(Box::new(EncodeError::MessageTooLarge { size: message_size, max_size }));
+        }
+    } else if message_size > WARNING_MESSAGE_SIZE {
+        eprintln!("{}", format!("Warning: the message has {} bytes, use --max-size to set a limit", message_size).bright_yellow());
+    }
+
+    if message_size > png_size {
+        eprintln!("{}", format!("Warning: the message ({} bytes) is larger than the original PNG ({} bytes)", message_size, png_size).bright_yellow());
+    }
 
     Ok(())
 }
 
 #[derive(Debug)]
 pub enum EncodeError {
-    MissingMessage
+    MissingMessage,
+    InvalidEntry(String),
+    MessageTooLarge {
+        size: usize,
+        max_size: usize
+    },
+
+    /// The chunk file has the contained number of bytes after the chunk.
+    ExtraChunkBytes(usize),
+
+    /// The ancillary chunks together with the message exceed the --max-embedded budget.
+    EmbeddedTooLarge {
+        size: usize,
+        max_size: usize
+    },
+
+    /// The resulting PNG does not parse back, which is reported by the contained message.
+    InvalidResult(String)
 }
 
 impl std::error::Error for EncodeError {}
@@ -113,8 +531,378 @@ impl Display for EncodeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::MissingMessage => {
-                write!(f, "{}", "Missing Message Error: one of --msg and --msg-file must be set")
+                write!(f, "Missing Message Error: one of --msg and --msg-file must be set")
+            },
+            Self::InvalidEntry(entry) => {
+                write!(f, "Invalid Entry Error: expected NAME=FILE but got {}", entry)
+            },
+            Self::MessageTooLarge { size, max_size } => {
+                write!(f, "Message Too Large Error: the message has {} bytes, exceeding the limit of {} bytes", size, max_size)
+            },
+            Self::ExtraChunkBytes(n_bytes) => {
+                write!(f, "Extra Chunk Bytes Error: the chunk file has {} bytes after the chunk", n_bytes)
+            },
+            Self::EmbeddedTooLarge { size, max_size } => {
+                write!(f, "Embedded Too Large Error: the ancillary chunks would have {} bytes of data, exceeding the limit of {} bytes", size, max_size)
+            },
+            Self::InvalidResult(reason) => {
+                write!(f, "Invalid Result Error: the encoded PNG is not written since it would be invalid: {}", reason)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::str::FromStr;
+    use clap::Parser;
+    use crate::cli::Cli;
+
+    fn testing_png_file(name: &str) -> PathBuf {
+        let chunk = Chunk::from_text(ChunkType::from_str("FrSt").unwrap(), "I am the first chunk");
+        let filepath = std::env::temp_dir().join(name);
+        File::create(&filepath).unwrap()
+            .write_all(&Png::from_chunks(vec![chunk]).as_bytes())
+            .unwrap();
+        filepath
+    }
+
+    #[test]
+    fn test_message_within_limit() {
+        assert!(check_message_size(10, 100, Some(10)).is_ok());
+        assert!(check_message_size(10, 100, None).is_ok());
+    }
+
+    #[test]
+    fn test_message_over_limit() {
+        let result = check_message_size(11, 100, Some(10));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("exceeding the limit of 10 bytes"));
+    }
+
+    #[test]
+    fn test_verified_bytes() {
+        let png = Png::from_chunks(vec![
+            Chunk::from_text(ChunkType::from_str("ruSt").unwrap(), "Message")
+        ]);
+        assert_eq!(verified_bytes(&png).unwrap(), png.as_bytes());
+    }
+
+    #[test]
+    fn test_invalid_result_is_not_written() {
+        // a chunk type with a non-letter byte cannot be parsed back
+        let png = Png::from_chunks(vec![
+            Chunk::from_text(ChunkType::from_bytes_lenient(*b"ru1t"), "Message")
+        ]);
+        let result = verified_bytes(&png);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Invalid Result Error"));
+    }
+
+    #[test]
+    fn test_force_crc() {
+        let filepath = testing_png_file("hackpng_test_force_crc.png");
+
+        let cli = Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "ruSt", "-m", "Message", "--force-crc", "0xdeadbeef"
+        ]).unwrap();
+        cli.run().unwrap();
+
+        let mut actual = Vec::new();
+        File::open(&filepath).unwrap().read_to_end(&mut actual).unwrap();
+        assert_eq!(actual[actual.len() - 4..], [0xde, 0xad, 0xbe, 0xef]);
+        assert!(Png::try_from(actual.as_slice()).is_err());
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_standard_type_warning() {
+        let warning = standard_type_warning("tEXt").unwrap();
+        assert!(warning.contains("tEXt is a standard chunk type"));
+        assert!(warning.contains("private type teXt"));
+        assert!(standard_type_warning("ruSt").is_none());
+
+        // the suggestion for a critical type is ancillary as well
+        let warning = standard_type_warning("IDAT").unwrap();
+        assert!(warning.contains("private type idAT"));
+
+        // a type which is already private and ancillary is not suggested as is
+        let warning = standard_type_warning("fdAT").unwrap();
+        assert!(warning.contains("private type fdAt"));
+    }
+
+    #[test]
+    fn test_parse_crc() {
+        assert_eq!(parse_crc("0xabd1d84e"), Ok(2882656334));
+        assert_eq!(parse_crc("2882656334"), Ok(2882656334));
+        assert!(parse_crc("0xzz").is_err());
+    }
+
+    #[test]
+    fn test_random_type() {
+        let filepath = testing_png_file("hackpng_test_random_type.png");
+
+        let cli = Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "--random-type", "-m", "Message"
+        ]).unwrap();
+        cli.run().unwrap();
+
+        let mut bytes = Vec::new();
+        File::open(&filepath).unwrap().read_to_end(&mut bytes).unwrap();
+        let png = Png::try_from(bytes.as_slice()).unwrap();
+        let chunk = png.chunk_at(1).unwrap();
+        assert!(chunk.chunk_type().is_valid());
+        assert!(!chunk.chunk_type().is_critical());
+        assert!(!chunk.chunk_type().is_public());
+        assert_eq!(&chunk.data_as_string().unwrap(), "Message");
+
+        assert!(Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "ruSt", "--random-type", "-m", "Message"
+        ]).is_err());
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_upsert() {
+        let filepath = testing_png_file("hackpng_test_upsert.png");
+
+        for message in ["First", "Second"] {
+            Cli::try_parse_from([
+                "hackpng", "encode", filepath.to_str().unwrap(), "ruSt", "-m", message, "--upsert"
+            ]).unwrap().run().unwrap();
+        }
+
+        let mut bytes = Vec::new();
+        File::open(&filepath).unwrap().read_to_end(&mut bytes).unwrap();
+        let png = Png::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(png.len(), 2);
+        assert_eq!(png.chunks_by_type("ruSt").count(), 1);
+        assert_eq!(png::decode_message(&png, "ruSt").unwrap(), Some(b"Second".to_vec()));
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_property_bits() {
+        let filepath = testing_png_file("hackpng_test_property_bits.png");
+
+        Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "RUST", "-m", "Message", "--ancillary", "--private"
+        ]).unwrap().run().unwrap();
+
+        let png = Png::from_path(&filepath).unwrap();
+        assert_eq!(png.chunk_at(1).unwrap().chunk_type().to_string(), "ruST");
+
+        // conflicting flags
+        assert!(Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "RUST", "-m", "Message", "--critical", "--ancillary"
+        ]).is_err());
+
+        // the flags only apply to an explicit chunk type
+        assert!(Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "-m", "Message", "--critical"
+        ]).is_err());
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_from_chunk_file() {
+        let filepath = testing_png_file("hackpng_test_from_chunk_file.png");
+        let chunk = Chunk::from_text(ChunkType::from_str("ruSt").unwrap(), "Pre-built");
+        let chunk_filepath = std::env::temp_dir().join("hackpng_test_from_chunk_file.chunk");
+        std::fs::write(&chunk_filepath, chunk.as_bytes()).unwrap();
+
+        Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(),
+            "--from-chunk-file", chunk_filepath.to_str().unwrap(), "-i", "0"
+        ]).unwrap().run().unwrap();
+
+        let png = Png::from_path(&filepath).unwrap();
+        assert_eq!(png.len(), 2);
+        assert_eq!(png.chunk_at(0).unwrap().as_bytes(), chunk.as_bytes());
+
+        // the CRC is checked
+        std::fs::write(&chunk_filepath, chunk.as_bytes_with_crc(0)).unwrap();
+        assert!(Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(),
+            "--from-chunk-file", chunk_filepath.to_str().unwrap()
+        ]).unwrap().run().is_err());
+        assert_eq!(Png::from_path(&filepath).unwrap().len(), 2);
+
+        std::fs::remove_file(filepath).unwrap();
+        std::fs::remove_file(chunk_filepath).unwrap();
+    }
+
+    #[test]
+    fn test_max_embedded() {
+        let filepath = testing_png_file("hackpng_test_max_embedded.png");
+
+        Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "ruSt", "-m", "Message", "--max-embedded", "10"
+        ]).unwrap().run().unwrap();
+        assert_eq!(Png::from_path(&filepath).unwrap().len(), 2);
+
+        // the existing message counts towards the budget
+        let result = Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "teSt", "-m", "Message", "--max-embedded", "10"
+        ]).unwrap().run();
+        assert!(result.unwrap_err().to_string().contains("14 bytes of data, exceeding the limit of 10 bytes"));
+        assert_eq!(Png::from_path(&filepath).unwrap().len(), 2);
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_preserve_mtime() {
+        let filepath = testing_png_file("hackpng_test_preserve_mtime.png");
+        let mtime = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+        File::options().write(true).open(&filepath).unwrap().set_modified(mtime).unwrap();
+
+        Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "ruSt", "-m", "Message", "--preserve-mtime"
+        ]).unwrap().run().unwrap();
+
+        assert_eq!(std::fs::metadata(&filepath).unwrap().modified().unwrap(), mtime);
+        assert_eq!(Png::from_path(&filepath).unwrap().len(), 2);
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_tagged_round_trip() {
+        let filepath = testing_png_file("hackpng_test_tagged_round_trip.png");
+        let output_filepath = std::env::temp_dir().join("hackpng_test_tagged_round_trip.txt");
+
+        Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "ruSt", "-m", "Message", "--tagged"
+        ]).unwrap().run().unwrap();
+
+        let png = Png::from_path(&filepath).unwrap();
+        assert!(tag::is_tagged(png.chunk_by_type("ruSt").unwrap().data()));
+
+        Cli::try_parse_from([
+            "hackpng", "decode", filepath.to_str().unwrap(), "ruSt", "--tagged",
+            "-o", output_filepath.to_str().unwrap()
+        ]).unwrap().run().unwrap();
+
+        let mut message = Vec::new();
+        File::open(&output_filepath).unwrap().read_to_end(&mut message).unwrap();
+        assert_eq!(message, b"Message");
+
+        std::fs::remove_file(filepath).unwrap();
+        std::fs::remove_file(output_filepath).unwrap();
+    }
+
+    #[test]
+    fn test_dry_run() {
+        let filepath = testing_png_file("hackpng_test_dry_run.png");
+        let mut original = Vec::new();
+        File::open(&filepath).unwrap().read_to_end(&mut original).unwrap();
+
+        let cli = Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "ruSt", "-m", "Message", "--dry-run"
+        ]).unwrap();
+        cli.run().unwrap();
+
+        let mut actual = Vec::new();
+        File::open(&filepath).unwrap().read_to_end(&mut actual).unwrap();
+        assert_eq!(actual, original);
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let filepath = testing_png_file("hackpng_test_hex_round_trip.png");
+
+        let cli = Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "ruSt", "-m", "deadbeef", "--hex"
+        ]).unwrap();
+        cli.run().unwrap();
+
+        let mut buffer = Vec::new();
+        File::open(&filepath).unwrap().read_to_end(&mut buffer).unwrap();
+        let png = Png::try_from(buffer.as_slice()).unwrap();
+        let chunk = png.chunk_by_type("ruSt").unwrap();
+        assert_eq!(chunk.data(), [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(hex::encode(chunk.data()), "deadbeef");
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_hex_message() {
+        let filepath = testing_png_file("hackpng_test_invalid_hex_message.png");
+
+        let cli = Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "ruSt", "-m", "deadbee", "--hex"
+        ]).unwrap();
+        assert!(cli.run().is_err());
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_progress_output() {
+        let filepath = testing_png_file("hackpng_test_progress_output.png");
+        let message_filepath = std::env::temp_dir().join("hackpng_test_progress_message.txt");
+        File::create(&message_filepath).unwrap()
+            .write_all(&vec![b'x'; 200_000])
+            .unwrap();
+
+        let mut outputs: Vec<Vec<u8>> = vec![];
+        for show_progress in [false, true] {
+            let output_filepath = std::env::temp_dir().join("hackpng_test_progress_output_out.png");
+            let mut cli_args = vec![
+                "hackpng", "encode", filepath.to_str().unwrap(), "ruSt",
+                "-f", message_filepath.to_str().unwrap(),
+                "-o", output_filepath.to_str().unwrap()
+            ];
+            if show_progress {
+                cli_args.push("--progress");
             }
+            Cli::try_parse_from(cli_args).unwrap().run().unwrap();
+
+            let mut buffer = Vec::new();
+            File::open(&output_filepath).unwrap().read_to_end(&mut buffer).unwrap();
+            outputs.push(buffer);
+            std::fs::remove_file(output_filepath).unwrap();
         }
+        assert_eq!(outputs[0], outputs[1]);
+
+        std::fs::remove_file(filepath).unwrap();
+        std::fs::remove_file(message_filepath).unwrap();
+    }
+
+    #[test]
+    fn test_new_png() {
+        let filepath = std::env::temp_dir().join("hackpng_test_new_png.png");
+        let _ = std::fs::remove_file(&filepath);
+
+        let cli = Cli::try_parse_from([
+            "hackpng", "encode", filepath.to_str().unwrap(), "ruSt", "-m", "Message", "--new", "1x1"
+        ]).unwrap();
+        cli.run().unwrap();
+
+        let mut buffer = Vec::new();
+        File::open(&filepath).unwrap().read_to_end(&mut buffer).unwrap();
+        let png = Png::try_from(buffer.as_slice()).unwrap();
+        assert_eq!(&png.chunk_at(0).unwrap().chunk_type().to_string(), "IHDR");
+        assert_eq!(png::decode_message(&png, "ruSt").unwrap(), Some(b"Message".to_vec()));
+
+        std::fs::remove_file(filepath).unwrap();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("640x480"), Ok((640, 480)));
+        assert!(parse_size("640").is_err());
+        assert!(parse_size("640xabc").is_err());
+    }
+}
@@ -0,0 +1,45 @@
+use std::{
+    io::Write,
+    fs::File,
+    path::PathBuf
+};
+
+use crate::{
+    Result,
+    png::Png
+};
+
+use super::filter::FilterArgs;
+
+#[derive(Debug, clap::Args)]
+pub struct ExtractArgs {
+
+    /// PNG file whose chunks are to extract
+    #[arg(value_name = "PNG")]
+    png_filepath: PathBuf,
+
+    /// Directory where the data of each chunk is written
+    /// into a file named <INDEX>_<TYPE>.bin
+    #[arg(short, long = "out", value_name = "OUTPUT_DIR")]
+    output_dirpath: PathBuf,
+
+    #[command(flatten)]
+    filter: FilterArgs
+
+}
+
+pub fn extract(args: ExtractArgs) -> Result<()> {
+
+    // read the PNG file
+    let png = Png::from_path(&args.png_filepath)?;
+
+    // write the data of the chunks passing the filter
+    for (index, chunk) in args.filter.apply(png.chunks()) {
+        let filepath = args.output_dirpath.join(format!("{}_{}.bin", index, chunk.chunk_type()));
+        File::create(&filepath)?
+            .write_all(chunk.data())?;
+        println!("Extracted {}", filepath.display());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use std::{
+    io::Write,
+    fs::File,
+    path::PathBuf
+};
+
+use colored::Colorize;
+
+use crate::{
+    Result,
+    png::{Png, PngError, EXIF}
+};
+
+#[derive(Debug, clap::Args)]
+pub struct ExtractExifArgs {
+
+    /// PNG file containing the EXIF metadata
+    #[arg(value_name = "PNG")]
+    png_filepath: PathBuf,
+
+    /// File where the raw EXIF data is written,
+    /// which defaults to the PNG file path with the extension .exif
+    #[arg(short, long = "out", value_name = "OUTPUT_FILE")]
+    output_filepath: Option<PathBuf>
+
+}
+
+pub fn extract_exif(args: ExtractExifArgs) -> Result<()> {
+
+    // read the PNG file
+    let png = Png::from_path(&args.png_filepath)?;
+
+    // locate the EXIF data
+    let exif = png.exif()
+        .ok_or_else(|| Box::new(PngError::ChunkNotFound(EXIF.to_string())))?;
+
+    // decide the output file path
+    let output_filepath = args.output_filepath
+        .unwrap_or_else(|| args.png_filepath.with_extension("exif"));
+
+    // wirte file
+    File::create(&output_filepath)?
+        .write_all(exif)?;
+
+    println!("{}", format!("Extracted {} bytes of EXIF data to {}", exif.len(), output_filepath.display()).bright_green());
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::str::FromStr;
+    use clap::Parser;
+    use crate::chunk::Chunk;
+    use crate::chunk_type::ChunkType;
+    use crate::cli::Cli;
+
+    #[test]
+    fn test_extract_exif() {
+        let exif_data = b"II\x2a\0\x08\0\0\0\0\0".to_vec();
+        let png = Png::from_chunks(vec![
+            Chunk::from_text(ChunkType::from_str("tEXt").unwrap(), "Comment\0text"),
+            Chunk::from_bytes_data(ChunkType::from_str(EXIF).unwrap(), exif_data.clone()),
+        ]);
+        let png_filepath = std::env::temp_dir().join("hackpng_test_extract_exif.png");
+        let output_filepath = std::env::temp_dir().join("hackpng_test_extract_exif.exif");
+        File::create(&png_filepath).unwrap().write_all(&png.as_bytes()).unwrap();
+
+        Cli::try_parse_from([
+            "hackpng", "extract-exif", png_filepath.to_str().unwrap()
+        ]).unwrap().run().unwrap();
+
+        let mut actual = Vec::new();
+        File::open(&output_filepath).unwrap().read_to_end(&mut actual).unwrap();
+        assert_eq!(actual, exif_data);
+
+        std::fs::remove_file(png_filepath).unwrap();
+        std::fs::remove_file(output_filepath).unwrap();
+    }
+
+    #[test]
+    fn test_missing_exif() {
+        let png = Png::from_chunks(vec![
+            Chunk::from_text(ChunkType::from_str("tEXt").unwrap(), "Comment\0text"),
+        ]);
+        let png_filepath = std::env::temp_dir().join("hackpng_test_missing_exif.png");
+        File::create(&png_filepath).unwrap().write_all(&png.as_bytes()).unwrap();
+
+        let result = Cli::try_parse_from([
+            "hackpng", "extract-exif", png_filepath.to_str().unwrap()
+        ]).unwrap().run();
+        assert!(result.is_err());
+
+        std::fs::remove_file(png_filepath).unwrap();
+    }
+}
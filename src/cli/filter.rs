@@ -0,0 +1,99 @@
+use crate::{
+    chunk::Chunk,
+    png::filter::ChunkFilter
+};
+
+/// Options to filter chunks by the property bits of their types and their data lengths.
+#[derive(Debug, clap::Args)]
+pub struct FilterArgs {
+
+    /// Only includes ancillary chunks
+    #[arg(long)]
+    ancillary: bool,
+
+    /// Only includes private chunks
+    #[arg(long)]
+    private: bool,
+
+    /// Only includes chunks which are safe to copy
+    #[arg(long)]
+    safe_to_copy: bool,
+
+    /// Only includes chunks with at least this many data bytes
+    #[arg(long, value_name = "BYTES")]
+    min_length: Option<u32>,
+
+    /// Only includes chunks with at most this many data bytes
+    #[arg(long, value_name = "BYTES")]
+    max_length: Option<u32>,
+
+    /// Only includes chunks whose types match the regular expression
+    #[cfg(feature = "regex")]
+    #[arg(long, value_name = "PATTERN", value_parser = regex::Regex::new)]
+    type_regex: Option<regex::Regex>
+
+}
+
+impl From<&FilterArgs> for ChunkFilter {
+    fn from(args: &FilterArgs) -> Self {
+        ChunkFilter {
+            ancillary: args.ancillary,
+            private: args.private,
+            safe_to_copy: args.safe_to_copy,
+            min_length: args.min_length,
+            max_length: args.max_length
+        }
+    }
+}
+
+impl FilterArgs {
+
+    /// Returns the chunks passing the filter together with their indices.
+    pub fn apply<'a>(&self, chunks: &'a [Chunk]) -> Vec<(usize, &'a Chunk)> {
+        ChunkFilter::from(self).apply_with(chunks, |chunk| self.matches_type_regex(chunk))
+    }
+
+    /// Checks if the chunk type matches the regular expression if any.
+    #[cfg(feature = "regex")]
+    fn matches_type_regex(&self, chunk: &Chunk) -> bool {
+        self.type_regex.as_ref().is_none_or(|type_regex| {
+            chunk.type_str().is_some_and(|chunk_type| type_regex.is_match(chunk_type))
+        })
+    }
+
+    #[cfg(not(feature = "regex"))]
+    fn matches_type_regex(&self, _chunk: &Chunk) -> bool {
+        true
+    }
+
+}
+
+
+#[cfg(all(test, feature = "regex"))]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_type_regex_filter() {
+        let chunks: Vec<Chunk> = ["IHDR", "tEXt", "ruSt", "ruST", "IDAT", "IEND"]
+            .iter()
+            .map(|chunk_type| Chunk::new(ChunkType::from_str(chunk_type).unwrap(), vec![]))
+            .collect();
+        let mut args = FilterArgs {
+            ancillary: false,
+            private: false,
+            safe_to_copy: false,
+            min_length: None,
+            max_length: None,
+            type_regex: Some(regex::Regex::new("^ru").unwrap())
+        };
+        let filtered: Vec<usize> = args.apply(&chunks).iter().map(|(index, _)| *index).collect();
+        assert_eq!(filtered, vec![2, 3]);
+
+        args.type_regex = Some(regex::Regex::new("^[A-Z]").unwrap());
+        args.ancillary = true;
+        assert!(args.apply(&chunks).is_empty());
+    }
+}
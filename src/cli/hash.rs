@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use sha2::{Sha256, Digest};
+
+use crate::{
+    Result,
+    crc,
+    hex,
+    png::{self, Png}
+};
+
+#[derive(Debug, clap::Args)]
+pub struct HashArgs {
+
+    /// PNG file to hash
+    #[arg(value_name = "PNG")]
+    png_filepath: PathBuf,
+
+    /// Hash algorithm to use
+    #[arg(short, long, value_enum, default_value_t = HashAlgorithm::Sha256)]
+    algorithm: HashAlgorithm,
+
+    /// If set, only the critical chunks (IHDR, PLTE, IDAT, IEND, etc.) are hashed,
+    /// so that changes to ancillary metadata do not affect the digest
+    #[arg(short, long)]
+    critical_only: bool
+
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum HashAlgorithm {
+    Sha256,
+    Crc32
+}
+
+pub fn hash(args: HashArgs) -> Result<()> {
+
+    // read the PNG file
+    let buffer = png::read_path(&args.png_filepath)?;
+
+    // bytes to hash
+    let bytes = if args.critical_only {
+        Png::try_from(buffer.as_slice())?.critical_bytes()
+    } else {
+        buffer
+    };
+
+    println!("{}", digest(&bytes, args.algorithm));
+
+    Ok(())
+}
+
+/// Computes the digest of the bytes as a lowercase hex string.
+fn digest(bytes: &[u8], algorithm: HashAlgorithm) -> String {
+    match algorithm {
+        HashAlgorithm::Sha256 => {
+            hex::encode(&Sha256::digest(bytes))
+        },
+        HashAlgorithm::Crc32 => {
+            format!("{:08x}", crc::crc32(bytes))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_digest() {
+        assert_eq!(
+            digest(b"abc", HashAlgorithm::Sha256),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_crc32_digest() {
+        assert_eq!(digest(b"abc", HashAlgorithm::Crc32), "352441c2");
+    }
+}
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::{
+    Result,
+    png::Png
+};
+
+use super::report;
+
+#[derive(Debug, clap::Args)]
+pub struct HuntArgs {
+
+    /// PNG file to hunt for messages
+    #[arg(value_name = "PNG")]
+    png_filepath: PathBuf,
+
+    /// If set, only this many of the most likely chunks are shown
+    #[arg(short = 'n', long, value_name = "N")]
+    top: Option<usize>,
+
+    /// Number of data bytes shown as a preview of each chunk
+    #[arg(long, value_name = "N", default_value_t = 40)]
+    preview: usize
+
+}
+
+pub fn hunt(args: HuntArgs) -> Result<()> {
+
+    // read the PNG file
+    let png = Png::from_path(&args.png_filepath)?;
+
+    let found = png.find_message_chunks();
+    if found.is_empty() {
+        eprintln!("{}", "No candidate chunks found".bright_yellow());
+        return Ok(());
+    }
+
+    let n_shown = args.top.unwrap_or(found.len());
+    for (rank, chunk) in found.iter().take(n_shown).enumerate() {
+        println!(
+            "{:>3}  {}  {:>10}  {:>5.1}%  {}",
+            rank + 1,
+            chunk.chunk_type().to_string().bright_cyan(),
+            chunk.data_len(),
+            chunk.printable_ratio() * 100.0,
+            report::text_preview(chunk.data(), args.preview)
+        );
+    }
+
+    Ok(())
+}
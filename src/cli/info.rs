@@ -0,0 +1,266 @@
+use std::path::PathBuf;
+
+use colored::Colorize;
+use serde_json::json;
+
+use crate::{
+    Result,
+    chunk::Chunk,
+    png::{self, Png, text}
+};
+
+use super::report::{self, OutputFormat};
+
+#[derive(Debug, clap::Args)]
+pub struct InfoArgs {
+
+    /// PNG file to inspect
+    #[arg(value_name = "PNG")]
+    png_filepath: PathBuf,
+
+    /// Output format of the report
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    output_format: OutputFormat,
+
+    /// If set, CRCs are shown as decimal numbers instead of hex strings
+    #[arg(long)]
+    decimal_crc: bool,
+
+    /// If set, the table shows the byte offset where each chunk starts in the file
+    #[arg(long)]
+    offsets: bool
+
+}
+
+pub fn info(args: InfoArgs) -> Result<()> {
+
+    // read the PNG file
+    let buffer = png::read_path(&args.png_filepath)?;
+
+    // create a Png object,
+    // keeping chunks with a mismatched CRC so that the round trip report can point them out
+    let (png, _) = Png::from_bytes_tolerant(&buffer)?;
+
+    match args.output_format {
+        OutputFormat::Table => {
+            print_table(&png, &buffer, args.offsets);
+        },
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&info_json(&png, &buffer, args.decimal_crc))?);
+        },
+        OutputFormat::Csv => {
+            let chunks: Vec<(usize, &Chunk)> = png.chunks().iter().enumerate().collect();
+            println!("{}", report::chunks_csv(&chunks, args.decimal_crc));
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints the report as a human-readable table.
+fn print_table(png: &Png, buffer: &[u8], offsets: bool) {
+
+    // list the chunks
+    for (index, (offset, chunk)) in png.chunk_offsets().into_iter().enumerate() {
+        let chunk_type = chunk.chunk_type().to_string();
+        let label = report::chunk_label(&chunk_type);
+        if offsets {
+            println!("{:>4}  {:#010x}  {}  {:>10} bytes  {}", index, offset, chunk_type, chunk.length(), label);
+        } else {
+            println!("{:>4}  {}  {:>10} bytes  {}", index, chunk_type, chunk.length(), label);
+        }
+    }
+
+    // summary
+    println!("{} chunks, {} bytes", png.len(), buffer.len());
+
+    // data sizes
+    let data_sizes = png.data_sizes();
+    println!(
+        "Data: {} bytes ({} critical, {} ancillary), metadata ratio {:.4}",
+        data_sizes.total,
+        data_sizes.critical,
+        data_sizes.ancillary,
+        data_sizes.metadata_ratio()
+    );
+
+    // compressed and international text
+    for chunk in png.chunks() {
+        match chunk.type_str() {
+            Some(text::ZTXT) => {
+                match text::ZtxtChunk::try_from(chunk) {
+                    Ok(ztxt) => {
+                        println!("{}: {}", ztxt.keyword(), ztxt.text());
+                    },
+                    Err(e) => {
+                        eprintln!("{}", e.to_string().bright_yellow());
+                    }
+                }
+            },
+            Some(text::ITXT) => {
+                match text::ItxtChunk::try_from(chunk) {
+                    Ok(itxt) => {
+                        println!("{} [{}]: {}", itxt.keyword(), itxt.language_tag(), itxt.text());
+                    },
+                    Err(e) => {
+                        eprintln!("{}", e.to_string().bright_yellow());
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+
+    // interlacing
+    match png.is_interlaced() {
+        Ok(true) => {
+            println!("Interlaced: Adam7");
+        },
+        Ok(false) => {
+            println!("Interlaced: no");
+        },
+        Err(e) => {
+            eprintln!("{}", e.to_string().bright_yellow());
+        }
+    }
+
+    // structural anomalies
+    println!("IDAT chunks: {}", png.chunks_by_type(png::IDAT).count());
+    for anomaly in png.anomalies() {
+        println!("{}", anomaly.to_string().bright_yellow());
+    }
+
+    // round trip
+    if let Some(mismatch) = png.roundtrip_mismatch(buffer) {
+        println!("{}", format!("Re-serializing does not reproduce the file exactly: {}", mismatch).bright_yellow());
+    }
+
+    // palette
+    if let Some(palette) = png.palette() {
+        match palette {
+            Ok(palette) => {
+                println!("Palette: {} entries", palette.len());
+            },
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_yellow());
+            }
+        }
+    }
+
+    // color management
+    if let Some(gamma) = png.gamma() {
+        match gamma {
+            Ok(gamma) => {
+                println!("Gamma: {:.5}", gamma.gamma());
+            },
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_yellow());
+            }
+        }
+    }
+    if let Some(srgb) = png.srgb() {
+        match srgb {
+            Ok(srgb) => {
+                println!("sRGB: {} rendering intent", srgb.rendering_intent);
+            },
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_yellow());
+            }
+        }
+    }
+    if let Some(chrm) = png.chromaticities() {
+        match chrm {
+            Ok(chrm) => {
+                let (x, y) = chrm.white_point_xy();
+                println!("Chromaticities: white point ({:.5}, {:.5})", x, y);
+            },
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_yellow());
+            }
+        }
+    }
+
+    // physical resolution
+    if let Some(phys) = png.physical_dimensions() {
+        match phys {
+            Ok(phys) => {
+                match phys.dpi() {
+                    Some((dpi_x, dpi_y)) => {
+                        println!("Resolution: {:.0} x {:.0} DPI", dpi_x, dpi_y);
+                    },
+                    None => {
+                        println!("Pixel aspect ratio: {}:{}", phys.pixels_per_unit_x, phys.pixels_per_unit_y);
+                    }
+                }
+            },
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_yellow());
+            }
+        }
+    }
+
+    // EXIF metadata
+    if let Some(exif) = png.exif() {
+        println!("EXIF: {} bytes", exif.len());
+    }
+
+    // animation
+    if let Some(actl) = png.animation_control() {
+        match actl {
+            Ok(actl) => {
+                println!("Animated: {} frames, {} plays", actl.num_frames, actl.num_plays);
+            },
+            Err(e) => {
+                eprintln!("{}", e.to_string().bright_yellow());
+            }
+        }
+    }
+}
+
+/// Builds the report as a JSON object.
+fn info_json(png: &Png, buffer: &[u8], decimal_crc: bool) -> serde_json::Value {
+
+    let chunks: Vec<(usize, &Chunk)> = png.chunks().iter().enumerate().collect();
+    let data_sizes = png.data_sizes();
+    let roundtrip_mismatch = png.roundtrip_mismatch(buffer);
+    let animation = png.animation_control()
+        .and_then(|actl| actl.ok())
+        .map(|actl| json!({
+            "num_frames": actl.num_frames,
+            "num_plays": actl.num_plays
+        }));
+
+    json!({
+        "chunks": report::chunks_json(&chunks, decimal_crc),
+        "chunk_count": png.len(),
+        "file_size": buffer.len(),
+        "data_sizes": {
+            "total": data_sizes.total,
+            "critical": data_sizes.critical,
+            "ancillary": data_sizes.ancillary,
+            "metadata_ratio": data_sizes.metadata_ratio()
+        },
+        "byte_exact_roundtrip": roundtrip_mismatch.is_none(),
+        "roundtrip_mismatch": roundtrip_mismatch.map(|mismatch| mismatch.to_string()),
+        "interlaced": png.is_interlaced().ok(),
+        "idat_count": png.chunks_by_type(png::IDAT).count(),
+        "anomalies": png.anomalies()
+            .iter()
+            .map(|anomaly| anomaly.to_string())
+            .collect::<Vec<_>>(),
+        "animation": animation,
+        "exif_length": png.exif().map(|exif| exif.len()),
+        "gamma": png.gamma()
+            .and_then(|gamma| gamma.ok())
+            .map(|gamma| gamma.gamma()),
+        "rendering_intent": png.srgb()
+            .and_then(|srgb| srgb.ok())
+            .map(|srgb| srgb.rendering_intent.to_string()),
+        "dpi": png.physical_dimensions()
+            .and_then(|phys| phys.ok())
+            .and_then(|phys| phys.dpi()),
+        "palette_entries": png.palette()
+            .and_then(|palette| palette.ok())
+            .map(|palette| palette.len())
+    })
+}
@@ -0,0 +1,198 @@
+use std::path::PathBuf;
+
+use crate::{
+    Result,
+    hex,
+    chunk::Chunk,
+    png::Png
+};
+
+use super::{
+    filter::FilterArgs,
+    report::{self, OutputFormat}
+};
+
+#[derive(Debug, clap::Args)]
+pub struct ListArgs {
+
+    /// PNG file whose chunks are to list
+    #[arg(value_name = "PNG")]
+    png_filepath: PathBuf,
+
+    #[command(flatten)]
+    filter: FilterArgs,
+
+    /// Output format of the chunk list
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    output_format: OutputFormat,
+
+    /// If set, only the first N chunks are listed
+    #[arg(long, value_name = "N")]
+    head: Option<usize>,
+
+    /// If set, only the last N chunks are listed
+    #[arg(long, value_name = "N")]
+    tail: Option<usize>,
+
+    /// If set, CRCs are shown as decimal numbers instead of hex strings
+    #[arg(long)]
+    decimal_crc: bool,
+
+    /// If set, the table shows the first N bytes of the data of each chunk as hex and ASCII
+    #[arg(long, value_name = "N")]
+    preview: Option<usize>
+
+}
+
+/// Prints a row of the chunk table,
+/// followed by a preview of the first bytes of the data if requested.
+fn print_chunk(index: usize, chunk: &Chunk, preview: Option<usize>) {
+    let chunk_type = chunk.chunk_type().to_string();
+    let label = report::chunk_label(&chunk_type);
+    match preview {
+        Some(n_bytes) => {
+            println!("{:>4}  {}  {:>10} bytes  {}  {}", index, chunk_type, chunk.length(), label, data_preview(chunk.data(), n_bytes));
+        },
+        None => {
+            println!("{:>4}  {}  {:>10} bytes  {}", index, chunk_type, chunk.length(), label);
+        }
+    }
+}
+
+/// Shows the first `n_bytes` bytes of the data as hex and as text (see [`report::text_preview`]),
+/// where an ellipsis marks that the data is truncated.
+fn data_preview(data: &[u8], n_bytes: usize) -> String {
+
+    let shown = &data[..data.len().min(n_bytes)];
+    let ellipsis = if data.len() > n_bytes { "..." } else { "" };
+
+    format!("{}{} |{}|", hex::encode(shown), ellipsis, report::text_preview(data, n_bytes))
+}
+
+/// Chunks kept by `--head` and `--tail` together with the number of omitted chunks in between.
+struct Truncated<T> {
+    head: Vec<T>,
+    omitted: usize,
+    tail: Vec<T>
+}
+
+/// Keeps the first `head` and the last `tail` items.
+/// All items are kept if neither is given or they overlap.
+fn truncate<T: Copy>(items: &[T], head: Option<usize>, tail: Option<usize>) -> Truncated<T> {
+
+    // keep all items if nothing is truncated
+    let n_kept = head.unwrap_or(0).saturating_add(tail.unwrap_or(0));
+    if (head.is_none() && tail.is_none()) || n_kept >= items.len() {
+        return Truncated {
+            head: items.to_vec(),
+            omitted: 0,
+            tail: vec![]
+        };
+    }
+
+    let head = head.unwrap_or(0);
+    let tail = tail.unwrap_or(0);
+
+    Truncated {
+        head: items[..head].to_vec(),
+        omitted: items.len() - n_kept,
+        tail: items[items.len() - tail..].to_vec()
+    }
+}
+
+pub fn list(args: ListArgs) -> Result<()> {
+
+    // read the PNG file
+    let png = Png::from_path(&args.png_filepath)?;
+
+    // chunks passing the filter
+    let chunks = args.filter.apply(png.chunks());
+
+    // keep only the first and last chunks if requested
+    let truncated = truncate(&chunks, args.head, args.tail);
+    let chunks: Vec<_> = truncated.head
+        .iter()
+        .chain(truncated.tail.iter())
+        .copied()
+        .collect();
+
+    // list the chunks
+    match args.output_format {
+        OutputFormat::Table => {
+            for (index, chunk) in &truncated.head {
+                print_chunk(*index, chunk, args.preview);
+            }
+            if truncated.omitted > 0 {
+                println!("... {} more ...", truncated.omitted);
+            }
+            for (index, chunk) in &truncated.tail {
+                print_chunk(*index, chunk, args.preview);
+            }
+        },
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&report::chunks_json(&chunks, args.decimal_crc))?);
+        },
+        OutputFormat::Csv => {
+            println!("{}", report::chunks_csv(&chunks, args.decimal_crc));
+        }
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::chunk_type::ChunkType;
+
+    fn many_chunks() -> Vec<Chunk> {
+        (0..200)
+            .map(|i| Chunk::new(ChunkType::from_str("fdAT").unwrap(), vec![i as u8]))
+            .collect()
+    }
+
+    #[test]
+    fn test_truncate_head() {
+        let chunks = many_chunks();
+        let truncated = truncate(&chunks.iter().collect::<Vec<_>>(), Some(5), None);
+        assert_eq!(truncated.head.len(), 5);
+        assert_eq!(truncated.omitted, 195);
+        assert!(truncated.tail.is_empty());
+    }
+
+    #[test]
+    fn test_truncate_head_and_tail() {
+        let chunks = many_chunks();
+        let indexed: Vec<(usize, &Chunk)> = chunks.iter().enumerate().collect();
+        let truncated = truncate(&indexed, Some(3), Some(2));
+        assert_eq!(truncated.head.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(truncated.omitted, 195);
+        assert_eq!(truncated.tail.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![198, 199]);
+    }
+
+    #[test]
+    fn test_truncate_huge_counts() {
+        let chunks = many_chunks();
+        let truncated = truncate(&chunks.iter().collect::<Vec<_>>(), Some(usize::MAX), Some(1));
+        assert_eq!(truncated.head.len(), 200);
+        assert_eq!(truncated.omitted, 0);
+    }
+
+    #[test]
+    fn test_data_preview() {
+        assert_eq!(data_preview(b"Hello, world!", 5), "48656c6c6f... |Hello...|");
+        assert_eq!(data_preview(b"Hi\x00", 8), "486900 |Hi.|");
+        assert_eq!(data_preview(b"", 8), " ||");
+        assert_eq!(data_preview(b"Hi", 0), "... |...|");
+    }
+
+    #[test]
+    fn test_no_truncation() {
+        let chunks = many_chunks();
+        let refs: Vec<&Chunk> = chunks.iter().collect();
+        assert_eq!(truncate(&refs, None, None).head.len(), 200);
+        assert_eq!(truncate(&refs, Some(150), Some(50)).omitted, 0);
+    }
+}
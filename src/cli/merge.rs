@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+
+use crate::{
+    Result,
+    png::Png
+};
+
+#[derive(Debug, clap::Args)]
+pub struct MergeArgs {
+
+    /// PNG file to merge the chunks into
+    #[arg(value_name = "BASE")]
+    base_png_filepath: PathBuf,
+
+    /// PNG file whose ancillary chunks are copied
+    #[arg(value_name = "OTHER")]
+    other_png_filepath: PathBuf,
+
+    /// If set, the merged PNG will be saved in this file path
+    #[arg(short, long = "out", value_name = "OUTPUT_FILE")]
+    output_png_filepath: Option<PathBuf>
+
+}
+
+pub fn merge(args: MergeArgs) -> Result<()> {
+
+    // read the PNG files
+    let mut png = Png::from_path(&args.base_png_filepath)?;
+    let other = Png::from_path(&args.other_png_filepath)?;
+
+    // copy the ancillary chunks
+    let n_copied = png.merge_ancillary_from(&other)?;
+    println!("Merged {} ancillary chunks", n_copied);
+
+    // decide the output file path
+    let output_png_filepath = if let Some(output_png_filepath) = args.output_png_filepath {
+        output_png_filepath
+    } else {
+        args.base_png_filepath
+    };
+
+    // wirte file
+    png.save(output_png_filepath)?;
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use clap::Parser;
+    use crate::chunk::Chunk;
+    use crate::chunk_type::ChunkType;
+    use crate::cli::Cli;
+    use crate::png::{self, Png};
+
+    #[test]
+    fn test_merge_text() {
+        let base = Png::new_blank(1, 1).unwrap();
+        let base_filepath = std::env::temp_dir().join("hackpng_test_merge_base.png");
+        base.save(&base_filepath).unwrap();
+
+        let mut other = Png::new_blank(2, 2).unwrap();
+        other.insert_chunk(1, Chunk::from_text(ChunkType::from_str("tEXt").unwrap(), "Comment\0Merged")).unwrap();
+        let other_filepath = std::env::temp_dir().join("hackpng_test_merge_other.png");
+        other.save(&other_filepath).unwrap();
+
+        Cli::try_parse_from([
+            "hackpng", "merge", base_filepath.to_str().unwrap(), other_filepath.to_str().unwrap()
+        ]).unwrap().run().unwrap();
+
+        let merged = Png::from_path(&base_filepath).unwrap();
+        let chunk_types: Vec<String> = merged.chunks()
+            .iter()
+            .map(|chunk| chunk.chunk_type().to_string())
+            .collect();
+        assert_eq!(chunk_types, vec![png::IHDR, png::IDAT, "tEXt", png::IEND]);
+        assert_eq!(merged.chunk_by_type("tEXt").unwrap().data(), b"Comment\0Merged");
+
+        // the image of the base is kept
+        assert_eq!(merged.critical_bytes(), base.critical_bytes());
+
+        std::fs::remove_file(base_filepath).unwrap();
+        std::fs::remove_file(other_filepath).unwrap();
+    }
+}
@@ -1,22 +1,111 @@
 mod subcommands;
 mod encode;
 mod decode;
+mod clean;
+mod validate;
+mod retype;
+mod remove;
+mod redact;
+mod normalize;
+mod merge;
+mod repair;
+mod filter;
+mod list;
+mod extract;
+mod extract_exif;
+mod strings;
+mod hunt;
+mod carve;
+mod progress;
+mod report;
+mod hash;
+mod info;
+mod capacity;
+#[cfg(feature = "watch")]
+mod watch;
 
-use crate::Result;
+use crate::{Result, Error, png::PngError};
+use decode::DecodeError;
 use encode::encode;
 use decode::decode;
+use clean::clean;
+use validate::validate;
+use retype::retype;
+use remove::remove;
+use redact::redact;
+use normalize::normalize;
+use merge::merge;
+use repair::repair;
+use list::list;
+use extract::extract;
+use extract_exif::extract_exif;
+use strings::strings;
+use hunt::hunt;
+use carve::carve;
+use hash::hash;
+use info::info;
+use capacity::capacity;
+#[cfg(feature = "watch")]
+use watch::watch;
 use subcommands::Command;
 
+/// Exit code of the binary when the requested message, entry or chunk is not found,
+/// following the convention of `grep`.
+pub const EXIT_NOT_FOUND: u8 = 1;
+
+/// Exit code of the binary on any other error,
+/// which is the same as the one for invalid arguments.
+pub const EXIT_FAILURE: u8 = 2;
+
+/// Returns the exit code of the binary for the error returned by [`Cli::run`].
+pub fn exit_code(error: &Error) -> u8 {
+    let is_not_found = matches!(
+        downcast_error::<DecodeError>(error),
+        Some(DecodeError::MessageNotFound(_) | DecodeError::EntryNotFound(_))
+    ) || matches!(
+        downcast_error::<PngError>(error),
+        Some(PngError::ChunkNotFound(_))
+    );
+
+    if is_not_found {
+        EXIT_NOT_FOUND
+    } else {
+        EXIT_FAILURE
+    }
+}
+
+/// Downcasts the error to the concrete type,
+/// which may be boxed once more when propagated with `?` from a `Result<_, Box<E>>`.
+fn downcast_error<E: std::error::Error + 'static>(error: &Error) -> Option<&E> {
+    error.downcast_ref::<E>()
+        .or_else(|| error.downcast_ref::<Box<E>>().map(|error| error.as_ref()))
+}
+
 #[derive(clap::Parser)]
 #[command(author, version, about)]
 pub struct Cli {
 
     #[command(subcommand)]
-    pub command: Option<Command>
+    pub command: Option<Command>,
+
+    /// Increases the verbosity of the logs written to stderr,
+    /// where -v shows debug logs and -vv shows trace logs
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8
 
 }
 
 impl Cli {
+
+    /// Level of the logs to show according to the verbosity.
+    pub fn log_level(&self) -> log::LevelFilter {
+        match self.verbose {
+            0 => log::LevelFilter::Warn,
+            1 => log::LevelFilter::Debug,
+            _ => log::LevelFilter::Trace
+        }
+    }
+
     pub fn run(self) -> Result<()> {
         if let Some(command) = self.command {
             match command {
@@ -25,6 +114,61 @@ impl Cli {
                 },
                 Command::Decode(args) => {
                     decode(args)
+                },
+                Command::Hash(args) => {
+                    hash(args)
+                },
+                Command::Info(args) => {
+                    info(args)
+                },
+                Command::Capacity(args) => {
+                    capacity(args)
+                },
+                Command::Clean(args) => {
+                    clean(args)
+                },
+                Command::Validate(args) => {
+                    validate(args)
+                },
+                Command::Retype(args) => {
+                    retype(args)
+                },
+                Command::Remove(args) => {
+                    remove(args)
+                },
+                Command::Redact(args) => {
+                    redact(args)
+                },
+                Command::Normalize(args) => {
+                    normalize(args)
+                },
+                Command::Merge(args) => {
+                    merge(args)
+                },
+                Command::Repair(args) => {
+                    repair(args)
+                },
+                Command::List(args) => {
+                    list(args)
+                },
+                Command::Extract(args) => {
+                    extract(args)
+                },
+                Command::ExtractExif(args) => {
+                    extract_exif(args)
+                },
+                Command::Strings(args) => {
+                    strings(args)
+                },
+                Command::Hunt(args) => {
+                    hunt(args)
+                },
+                Command::Carve(args) => {
+                    carve(args)
+                },
+                #[cfg(feature = "watch")]
+                Command::Watch(args) => {
+                    watch(args)
                 }
             }
         } else {
@@ -0,0 +1,40 @@
+use std::path::PathBuf;
+
+use crate::{
+    Result,
+    png::Png
+};
+
+#[derive(Debug, clap::Args)]
+pub struct NormalizeArgs {
+
+    /// PNG file to normalize
+    #[arg(value_name = "PNG")]
+    input_png_filepath: PathBuf,
+
+    /// If set, the modified PNG will be saved in this file path
+    #[arg(short, long = "out", value_name = "OUTPUT_FILE")]
+    output_png_filepath: Option<PathBuf>
+
+}
+
+pub fn normalize(args: NormalizeArgs) -> Result<()> {
+
+    // read the PNG file
+    let mut png = Png::from_path(&args.input_png_filepath)?;
+
+    // reorder the chunks
+    png.normalize_ordering()?;
+
+    // decide the output file path
+    let output_png_filepath = if let Some(output_png_filepath) = args.output_png_filepath {
+        output_png_filepath
+    } else {
+        args.input_png_filepath
+    };
+
+    // wirte file
+    png.save(output_png_filepath)?;
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use std::{
+    io::{Read, Write, IsTerminal},
+    fs::File,
+    path::Path
+};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+use crate::{Result, atomic};
+
+/// Number of bytes read or written at a time.
+const BLOCK_SIZE: usize = 64 * 1024;
+
+/// Creates a progress bar for the given number of bytes.
+/// The progress bar is hidden if it is not requested or stderr is not a terminal.
+fn progress_bar(n_bytes: u64, message: &str, show: bool) -> ProgressBar {
+
+    if !show || !std::io::stderr().is_terminal() {
+        return ProgressBar::hidden();
+    }
+
+    let bar = ProgressBar::new(n_bytes);
+    bar.set_style(
+        ProgressStyle::with_template("{msg} [{bar:40}] {bytes}/{total_bytes}")
+            .unwrap()
+            .progress_chars("=> ")
+    );
+    bar.set_message(message.to_string());
+
+    bar
+}
+
+/// Reads the whole file, optionally showing the progress.
+pub fn read_file<P: AsRef<Path>>(path: P, show: bool) -> Result<Vec<u8>> {
+
+    let mut f = File::open(path)?;
+    let n_bytes = f.metadata()?.len();
+    let bar = progress_bar(n_bytes, "Reading", show);
+
+    let mut bytes: Vec<u8> = Vec::with_capacity(n_bytes as usize);
+    let mut block = vec![0; BLOCK_SIZE];
+    loop {
+        let n = f.read(&mut block)?;
+        if n == 0 {
+            break;
+        }
+        bytes.extend_from_slice(&block[..n]);
+        bar.inc(n as u64);
+    }
+    bar.finish_and_clear();
+
+    Ok(bytes)
+}
+
+/// Writes the bytes to the file, optionally showing the progress.
+/// The file is replaced atomically, see [`atomic::write_file`].
+pub fn write_file<P: AsRef<Path>>(path: P, bytes: &[u8], show: bool) -> Result<()> {
+
+    let bar = progress_bar(bytes.len() as u64, "Writing", show);
+
+    atomic::write_file(path, |f| {
+        for block in bytes.chunks(BLOCK_SIZE) {
+            f.write_all(block)?;
+            bar.inc(block.len() as u64);
+        }
+        Ok(())
+    })?;
+    bar.finish_and_clear();
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use crate::{
+    Result,
+    png::Png
+};
+
+#[derive(Debug, clap::Args)]
+pub struct RedactArgs {
+
+    /// PNG file containing the chunk
+    #[arg(value_name = "PNG")]
+    input_png_filepath: PathBuf,
+
+    /// Type of the chunk whose data is to be zeroed
+    chunk_type: String,
+
+    /// If set, the modified PNG will be saved in this file path
+    #[arg(short, long = "out", value_name = "OUTPUT_FILE")]
+    output_png_filepath: Option<PathBuf>
+
+}
+
+pub fn redact(args: RedactArgs) -> Result<()> {
+
+    // read the PNG file
+    let mut png = Png::from_path(&args.input_png_filepath)?;
+
+    // zero the chunk data
+    png.redact_chunk(&args.chunk_type)?;
+
+    // decide the output file path
+    let output_png_filepath = if let Some(output_png_filepath) = args.output_png_filepath {
+        output_png_filepath
+    } else {
+        args.input_png_filepath
+    };
+
+    // wirte file
+    png.save(output_png_filepath)?;
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use clap::Parser;
+    use crate::chunk::Chunk;
+    use crate::chunk_type::ChunkType;
+    use crate::cli::Cli;
+
+    #[test]
+    fn test_redact() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.insert_chunk(1, Chunk::from_text(ChunkType::from_str("ruSt").unwrap(), "Secret")).unwrap();
+        let filepath = std::env::temp_dir().join("hackpng_test_redact.png");
+        png.save(&filepath).unwrap();
+
+        Cli::try_parse_from([
+            "hackpng", "redact", filepath.to_str().unwrap(), "ruSt"
+        ]).unwrap().run().unwrap();
+
+        // parsing checks the CRC of each chunk
+        let redacted = Png::from_path(&filepath).unwrap();
+        let chunk = redacted.chunk_at(1).unwrap();
+        assert_eq!(chunk.type_str(), Some("ruSt"));
+        assert_eq!(chunk.data(), [0; 6]);
+        assert_eq!(redacted.len(), png.len());
+
+        // critical chunks are kept intact
+        assert!(Cli::try_parse_from([
+            "hackpng", "redact", filepath.to_str().unwrap(), "IDAT"
+        ]).unwrap().run().is_err());
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::{
+    Result,
+    png::Png
+};
+
+#[derive(Debug, clap::Args)]
+pub struct RemoveArgs {
+
+    /// PNG file containing the chunk
+    #[arg(value_name = "PNG")]
+    input_png_filepath: PathBuf,
+
+    /// Type of the chunk to remove, where the first matching chunk is removed
+    #[arg(required_unless_present = "index")]
+    chunk_type: Option<String>,
+
+    /// If set, the chunk at this index is removed instead,
+    /// which can be looked up by the list command
+    #[arg(short, long, conflicts_with = "chunk_type")]
+    index: Option<usize>,
+
+    /// If set, the modified PNG will be saved in this file path
+    #[arg(short, long = "out", value_name = "OUTPUT_FILE")]
+    output_png_filepath: Option<PathBuf>
+
+}
+
+pub fn remove(args: RemoveArgs) -> Result<()> {
+
+    // read the PNG file
+    let mut png = Png::from_path(&args.input_png_filepath)?;
+
+    // remove the chunk
+    let chunk = match (args.index, &args.chunk_type) {
+        (Some(index), _) => png.remove_chunk_at(index)?,
+        (None, Some(chunk_type)) => png.remove_chunk(chunk_type)?,
+        (None, None) => unreachable!("either the chunk type or the index is required")
+    };
+
+    println!("Removed chunk {} ({} bytes)", chunk.chunk_type().to_string().bright_green(), chunk.length());
+
+    // decide the output file path
+    let output_png_filepath = if let Some(output_png_filepath) = args.output_png_filepath {
+        output_png_filepath
+    } else {
+        args.input_png_filepath
+    };
+
+    // wirte file
+    png.save(output_png_filepath)?;
+
+    Ok(())
+}
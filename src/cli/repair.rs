@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::{
+    Result,
+    png::{self, repair}
+};
+
+#[derive(Debug, clap::Args)]
+pub struct RepairArgs {
+
+    /// PNG file to repair
+    #[arg(value_name = "PNG")]
+    input_png_filepath: PathBuf,
+
+    /// If set, the repaired PNG will be saved in this file path
+    #[arg(short, long = "out", value_name = "OUTPUT_FILE")]
+    output_png_filepath: Option<PathBuf>
+
+}
+
+pub fn repair(args: RepairArgs) -> Result<()> {
+
+    // read the file, which may not parse as a PNG
+    let bytes = png::read_path(&args.input_png_filepath)?;
+
+    // repair the structure
+    let (png, repairs) = repair::repair(&bytes)?;
+
+    // report the repairs
+    if repairs.is_empty() {
+        println!("{}", "Nothing to repair".bright_green());
+    }
+    for repair in &repairs {
+        println!("{}", repair);
+    }
+
+    // decide the output file path
+    let output_png_filepath = if let Some(output_png_filepath) = args.output_png_filepath {
+        output_png_filepath
+    } else {
+        args.input_png_filepath
+    };
+
+    // wirte file
+    png.save(output_png_filepath)?;
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use clap::Parser;
+    use crate::chunk::Chunk;
+    use crate::chunk_type::ChunkType;
+    use crate::cli::Cli;
+    use crate::png::{Png, IEND};
+
+    #[test]
+    fn test_repair_missing_iend() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.append_chunk(Chunk::from_text(ChunkType::from_str("ruSt").unwrap(), "Message"));
+        png.remove_chunk(IEND).unwrap();
+        let filepath = std::env::temp_dir().join("hackpng_test_repair_missing_iend.png");
+        std::fs::write(&filepath, png.as_bytes()).unwrap();
+        assert!(!Png::from_path(&filepath).unwrap().contains_type(IEND));
+
+        Cli::try_parse_from([
+            "hackpng", "repair", filepath.to_str().unwrap()
+        ]).unwrap().run().unwrap();
+
+        let repaired = Png::from_bytes(&std::fs::read(&filepath).unwrap(), true).unwrap();
+        assert!(repaired.is_structurally_valid());
+        assert_eq!(repaired.chunks().last().unwrap().chunk_type().to_string(), IEND);
+        assert_eq!(repaired.chunk_by_type("ruSt").unwrap().data(), b"Message");
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+}
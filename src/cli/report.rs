@@ -0,0 +1,152 @@
+use serde_json::{json, Value};
+
+use crate::{
+    crc,
+    chunk::{self, Chunk},
+    png::{self, apng}
+};
+
+/// Output format of the reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv
+}
+
+/// Header of the CSV report of chunks.
+const CSV_HEADER: &str = "index,type,length,crc,critical,public,safe_to_copy";
+
+/// Returns a short description of the chunk type if it is worth pointing out,
+/// otherwise an empty string.
+pub fn chunk_label(chunk_type: &str) -> &'static str {
+    match chunk_type {
+        png::EXIF => "EXIF metadata",
+        _ => apng::label(chunk_type).unwrap_or_default()
+    }
+}
+
+/// Shows the first `n_bytes` bytes of the data as text,
+/// where each byte that is not printable (see [`chunk::is_printable`]) is shown as a dot,
+/// and so is a tab, which would break the alignment of the table,
+/// and an ellipsis marks that the data is truncated.
+pub fn text_preview(data: &[u8], n_bytes: usize) -> String {
+
+    let shown: String = data[..data.len().min(n_bytes)]
+        .iter()
+        .map(|byte| if chunk::is_printable(*byte) && *byte != b'\t' { *byte as char } else { '.' })
+        .collect();
+    let ellipsis = if data.len() > n_bytes { "..." } else { "" };
+
+    format!("{}{}", shown, ellipsis)
+}
+
+/// Reports the chunk as a JSON object.
+/// The CRC is a hex string unless `decimal_crc` is set.
+pub fn chunk_json(index: usize, chunk: &Chunk, decimal_crc: bool) -> Value {
+    let chunk_type = chunk.chunk_type();
+    let crc = if decimal_crc {
+        json!(chunk.crc())
+    } else {
+        json!(crc::format_crc(chunk.crc()))
+    };
+    json!({
+        "index": index,
+        "type": chunk_type.to_string(),
+        "length": chunk.length(),
+        "crc": crc,
+        "critical": chunk_type.is_critical(),
+        "public": chunk_type.is_public(),
+        "safe_to_copy": chunk_type.is_safe_to_copy()
+    })
+}
+
+/// Reports the chunks as a JSON array.
+pub fn chunks_json(chunks: &[(usize, &Chunk)], decimal_crc: bool) -> Value {
+    Value::Array(
+        chunks.iter()
+            .map(|(index, chunk)| chunk_json(*index, chunk, decimal_crc))
+            .collect()
+    )
+}
+
+/// Reports the chunks as CSV with a header line.
+/// The CRC is a hex string unless `decimal_crc` is set.
+pub fn chunks_csv(chunks: &[(usize, &Chunk)], decimal_crc: bool) -> String {
+
+    let mut lines: Vec<String> = vec![CSV_HEADER.to_string()];
+
+    for (index, chunk) in chunks {
+        let chunk_type = chunk.chunk_type();
+        let crc = if decimal_crc {
+            chunk.crc().to_string()
+        } else {
+            crc::format_crc(chunk.crc())
+        };
+        lines.push(format!(
+            "{},{},{},{},{},{},{}",
+            index,
+            chunk_type,
+            chunk.length(),
+            crc,
+            chunk_type.is_critical(),
+            chunk_type.is_public(),
+            chunk_type.is_safe_to_copy()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    fn testing_chunk() -> Chunk {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        Chunk::from_text(chunk_type, "This is where your secret message will be!")
+    }
+
+    #[test]
+    fn test_chunk_label() {
+        assert_eq!(chunk_label("eXIf"), "EXIF metadata");
+        assert_eq!(chunk_label("acTL"), "animation control");
+        assert_eq!(chunk_label("IDAT"), "");
+    }
+
+    #[test]
+    fn test_text_preview() {
+        assert_eq!(text_preview(b"FLAG{x}", 40), "FLAG{x}");
+        assert_eq!(text_preview(b"ab\x00\ncd\tef", 7), "ab..cd....");
+        assert_eq!(text_preview(b"", 4), "");
+    }
+
+    #[test]
+    fn test_chunks_csv() {
+        let chunk = testing_chunk();
+        let csv = chunks_csv(&[(0, &chunk)], false);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "index,type,length,crc,critical,public,safe_to_copy");
+        assert_eq!(lines[1], "0,RuSt,42,0xabd1d84e,true,false,true");
+
+        let csv = chunks_csv(&[(0, &chunk)], true);
+        assert_eq!(csv.lines().nth(1).unwrap(), "0,RuSt,42,2882656334,true,false,true");
+    }
+
+    #[test]
+    fn test_chunks_json() {
+        let chunk = testing_chunk();
+        let report = chunks_json(&[(3, &chunk)], false);
+        assert_eq!(report[0]["index"], 3);
+        assert_eq!(report[0]["type"], "RuSt");
+        assert_eq!(report[0]["crc"], "0xabd1d84e");
+        assert_eq!(report[0]["safe_to_copy"], true);
+
+        let report = chunks_json(&[(3, &chunk)], true);
+        assert_eq!(report[0]["crc"], 2882656334u32);
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use crate::{
+    Result,
+    png::Png
+};
+
+#[derive(Debug, clap::Args)]
+pub struct RetypeArgs {
+
+    /// PNG file containing the chunk
+    #[arg(value_name = "PNG")]
+    input_png_filepath: PathBuf,
+
+    /// Current type of the chunk
+    old_chunk_type: String,
+
+    /// New type of the chunk
+    new_chunk_type: String,
+
+    /// If set, the modified PNG will be saved in this file path
+    #[arg(short, long = "out", value_name = "OUTPUT_FILE")]
+    output_png_filepath: Option<PathBuf>
+
+}
+
+pub fn retype(args: RetypeArgs) -> Result<()> {
+
+    // read the PNG file
+    let mut png = Png::from_path(&args.input_png_filepath)?;
+
+    // change the chunk type
+    png.retype_chunk(&args.old_chunk_type, &args.new_chunk_type)?;
+
+    // decide the output file path
+    let output_png_filepath = if let Some(output_png_filepath) = args.output_png_filepath {
+        output_png_filepath
+    } else {
+        args.input_png_filepath
+    };
+
+    // wirte file
+    png.save(output_png_filepath)?;
+
+    Ok(())
+}
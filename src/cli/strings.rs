@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+
+use colored::Colorize;
+
+use crate::{
+    Result,
+    chunk,
+    png::Png
+};
+
+#[derive(Debug, clap::Args)]
+pub struct StringsArgs {
+
+    /// PNG file to scan
+    #[arg(value_name = "PNG")]
+    png_filepath: PathBuf,
+
+    /// Minimum number of printable characters in a string
+    #[arg(short = 'n', long, value_name = "N", default_value_t = 4)]
+    min_len: usize
+
+}
+
+/// A run of printable ASCII characters found in the data of a chunk.
+#[derive(Debug, PartialEq, Eq)]
+struct Found {
+    /// Index of the chunk.
+    index: usize,
+
+    /// Type of the chunk.
+    chunk_type: String,
+
+    /// Offset of the string in the chunk data.
+    offset: usize,
+
+    text: String
+}
+
+pub fn strings(args: StringsArgs) -> Result<()> {
+
+    // read the PNG file
+    let png = Png::from_path(&args.png_filepath)?;
+
+    for found in find_strings(&png, args.min_len) {
+        println!(
+            "{:>4}  {}  {:>10}  {}",
+            found.index, found.chunk_type.bright_cyan(), found.offset, found.text
+        );
+    }
+
+    Ok(())
+}
+
+/// Finds the strings of at least `min_len` printable characters in the data of all chunks.
+fn find_strings(png: &Png, min_len: usize) -> Vec<Found> {
+    png.chunks()
+        .iter()
+        .enumerate()
+        .flat_map(|(index, chunk)| {
+            printable_runs(chunk.data(), min_len)
+                .into_iter()
+                .map(move |(offset, text)| Found {
+                    index,
+                    chunk_type: chunk.chunk_type().to_string(),
+                    offset,
+                    text
+                })
+        })
+        .collect()
+}
+
+/// Scans the bytes for runs of printable ASCII characters (including spaces and tabs)
+/// of at least `min_len` characters, like the Unix `strings` tool.
+/// Returns the offset and the text of each run.
+fn printable_runs(bytes: &[u8], min_len: usize) -> Vec<(usize, String)> {
+
+    let mut runs: Vec<(usize, String)> = vec![];
+
+    // start of the current run
+    let mut start = 0;
+    for (i, byte) in bytes.iter().chain([0].iter()).enumerate() {
+        if chunk::is_printable(*byte) {
+            continue;
+        }
+
+        // the run ends before a non-printable byte or the end of the bytes
+        if i - start >= min_len.max(1) {
+            runs.push((start, String::from_utf8_lossy(&bytes[start..i]).into_owned()));
+        }
+        start = i + 1;
+    }
+
+    runs
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::chunk::Chunk;
+    use crate::chunk_type::ChunkType;
+
+    #[test]
+    fn test_printable_runs() {
+        assert_eq!(
+            printable_runs(b"\x00abc\x01defg\x02hi jk", 4),
+            vec![(5, "defg".to_string()), (10, "hi jk".to_string())]
+        );
+        assert!(printable_runs(b"", 4).is_empty());
+    }
+
+    #[test]
+    fn test_find_flag() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.insert_chunk(1, Chunk::new(
+            ChunkType::from_str("ruSt").unwrap(),
+            b"\x89\x00\x01FLAG{hidden}\xff".to_vec()
+        )).unwrap();
+        png.insert_chunk(2, Chunk::from_text(ChunkType::from_str("teSt").unwrap(), "FLAG{second}")).unwrap();
+
+        let flags: Vec<Found> = find_strings(&png, 4)
+            .into_iter()
+            .filter(|found| found.text.starts_with("FLAG{"))
+            .collect();
+        assert_eq!(flags, vec![
+            Found { index: 1, chunk_type: "ruSt".to_string(), offset: 3, text: "FLAG{hidden}".to_string() },
+            Found { index: 2, chunk_type: "teSt".to_string(), offset: 0, text: "FLAG{second}".to_string() }
+        ]);
+
+        assert!(find_strings(&png, 13).is_empty());
+    }
+}
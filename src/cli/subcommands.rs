@@ -1,7 +1,26 @@
 use super::{
     encode::EncodeArgs,
-    decode::DecodeArgs
+    decode::DecodeArgs,
+    hash::HashArgs,
+    info::InfoArgs,
+    capacity::CapacityArgs,
+    clean::CleanArgs,
+    validate::ValidateArgs,
+    retype::RetypeArgs,
+    remove::RemoveArgs,
+    redact::RedactArgs,
+    normalize::NormalizeArgs,
+    merge::MergeArgs,
+    repair::RepairArgs,
+    list::ListArgs,
+    extract::ExtractArgs,
+    extract_exif::ExtractExifArgs,
+    strings::StringsArgs,
+    hunt::HuntArgs,
+    carve::CarveArgs
 };
+#[cfg(feature = "watch")]
+use super::watch::WatchArgs;
 
 #[derive(clap::Subcommand)]
 pub enum Command {
@@ -10,7 +29,62 @@ pub enum Command {
     Encode(EncodeArgs),
 
     /// Decodes the message from the PNG file
-    Decode(DecodeArgs)
+    Decode(DecodeArgs),
+
+    /// Computes a digest of the PNG file
+    Hash(HashArgs),
+
+    /// Shows the chunks and a summary of the PNG file
+    Info(InfoArgs),
+
+    /// Estimates how much data can be embedded into the PNG file
+    Capacity(CapacityArgs),
+
+    /// Cleans up the PNG file
+    Clean(CleanArgs),
+
+    /// Validates the chunks of the PNG file
+    Validate(ValidateArgs),
+
+    /// Changes the type of a chunk while keeping its data
+    Retype(RetypeArgs),
+
+    /// Removes a chunk by its type or index
+    Remove(RemoveArgs),
+
+    /// Zeroes the data of a chunk while keeping the chunk in place
+    Redact(RedactArgs),
+
+    /// Reorders the chunks to follow the PNG specification
+    Normalize(NormalizeArgs),
+
+    /// Copies the ancillary chunks of another PNG file into the PNG file
+    Merge(MergeArgs),
+
+    /// Repairs the structure of a slightly broken PNG file
+    Repair(RepairArgs),
+
+    /// Lists the chunks of the PNG file
+    List(ListArgs),
+
+    /// Extracts the data of the chunks into files
+    Extract(ExtractArgs),
+
+    /// Writes the raw EXIF data of the PNG file into a file
+    ExtractExif(ExtractExifArgs),
+
+    /// Prints the printable strings in the data of all chunks, like the Unix strings tool
+    Strings(StringsArgs),
+
+    /// Ranks the private ancillary chunks by how likely they hide a message
+    Hunt(HuntArgs),
+
+    /// Finds PNG files embedded in the file by their signatures and carves them out
+    Carve(CarveArgs),
+
+    /// Re-encodes the message file into the PNG file whenever it changes
+    #[cfg(feature = "watch")]
+    Watch(WatchArgs)
 
 }
 
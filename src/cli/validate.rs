@@ -0,0 +1,89 @@
+use std::{
+    path::PathBuf,
+    fmt::Display
+};
+
+use colored::Colorize;
+
+use crate::{
+    Result,
+    png::{self, Png}
+};
+
+#[derive(Debug, clap::Args)]
+pub struct ValidateArgs {
+
+    /// PNG file to validate
+    #[arg(value_name = "PNG")]
+    png_filepath: PathBuf
+
+}
+
+pub fn validate(args: ValidateArgs) -> Result<()> {
+
+    // read the PNG file
+    let buffer = png::read_path(&args.png_filepath)?;
+
+    // create a Png object leniently so that invalid chunks can be reported
+    let png = Png::from_bytes(buffer.as_slice(), false)?;
+
+    // problems found
+    let mut issues: Vec<String> = vec![];
+
+    // check each chunk
+    for (index, chunk) in png.chunks().iter().enumerate() {
+        if !chunk.is_valid() {
+            issues.push(format!("Chunk {} ({:?}) is invalid", index, chunk.chunk_type().bytes()));
+        }
+    }
+
+    // check the chunk layout
+    if let Err(e) = png.validate_structure() {
+        issues.push(e.to_string());
+    }
+
+    // check the image data and the trailer
+    for anomaly in png.anomalies() {
+        issues.push(anomaly.to_string());
+    }
+
+    // check the checksum of the image data
+    if png.chunks_by_type(png::IDAT).next().is_some() {
+        if let Err(e) = png.verify_idat_adler32() {
+            issues.push(e.to_string());
+        }
+    }
+
+    // check duplicated chunks
+    if let Err(e) = png.validate_uniqueness() {
+        issues.push(e.to_string());
+    }
+
+    if !issues.is_empty() {
+        for issue in &issues {
+            println!("{}", issue.bright_red());
+        }
+        return Err(Box::new(ValidateError::Issues(issues.len())));
+    }
+
+    println!("{}", "The PNG file is valid".bright_green());
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum ValidateError {
+    Issues(usize)
+}
+
+impl std::error::Error for ValidateError {}
+
+impl Display for ValidateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Issues(n_issues) => {
+                write!(f, "Validation Error: found {} issues", n_issues)
+            }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    time::Duration
+};
+
+use colored::Colorize;
+use notify::{Watcher, RecursiveMode, EventKind};
+
+use super::encode::{encode, EncodeArgs};
+
+use crate::Result;
+
+#[derive(Debug, clap::Args)]
+pub struct WatchArgs {
+
+    /// PNG file where the message is to encode
+    #[arg(value_name = "PNG")]
+    input_png_filepath: PathBuf,
+
+    /// Chunk type corresponding to the messsage chunk
+    chunk_type: String,
+
+    /// File containing the message, which is re-encoded whenever it changes
+    #[arg(short = 'f', long = "msg-file", value_name = "MESSAGE_FILE")]
+    message_filepath: PathBuf,
+
+    /// The PNG with encoded message will be saved in this file path
+    #[arg(short, long = "out", value_name = "OUTPUT_FILE")]
+    output_png_filepath: PathBuf,
+
+    /// Changes within this many milliseconds are treated as a single update
+    #[arg(long, value_name = "MILLISECONDS", default_value_t = 200)]
+    debounce: u64
+
+}
+
+pub fn watch(args: WatchArgs) -> Result<()> {
+
+    // forward the modifications of the message file
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+                let _ = sender.send(());
+            }
+        }
+    })?;
+    watcher.watch(&args.message_filepath, RecursiveMode::NonRecursive)?;
+
+    println!("Watching {}", args.message_filepath.display());
+
+    // re-encode on each update
+    run(receiver, Duration::from_millis(args.debounce), || {
+        encode(EncodeArgs::from_message_file(
+            args.input_png_filepath.clone(),
+            args.chunk_type.clone(),
+            args.message_filepath.clone(),
+            args.output_png_filepath.clone()
+        ))?;
+        println!("{} {}", "Updated".bright_green(), args.output_png_filepath.display());
+        Ok(())
+    })
+}
+
+/// Calls `on_change` once for each burst of change events,
+/// where events arriving within `debounce` of each other form a single burst.
+/// Returns when the sender of the events is dropped.
+fn run(receiver: Receiver<()>, debounce: Duration, mut on_change: impl FnMut() -> Result<()>) -> Result<()> {
+
+    // wait for the first event of a burst
+    while receiver.recv().is_ok() {
+
+        // swallow the rest of the burst
+        let disconnected = loop {
+            match receiver.recv_timeout(debounce) {
+                Ok(()) => continue,
+                Err(RecvTimeoutError::Timeout) => break false,
+                Err(RecvTimeoutError::Disconnected) => break true
+            }
+        };
+
+        // a failed update is reported without stopping the watch
+        if let Err(e) = on_change() {
+            eprintln!("{}", e.to_string().bright_red());
+        }
+
+        if disconnected {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_change_triggers_one_update() {
+        let (sender, receiver) = mpsc::channel();
+
+        // a burst of events as produced by a single save
+        for _ in 0..3 {
+            sender.send(()).unwrap();
+        }
+        drop(sender);
+
+        let mut count = 0;
+        run(receiver, Duration::from_millis(10), || {
+            count += 1;
+            Ok(())
+        }).unwrap();
+        assert_eq!(count, 1);
+    }
+}
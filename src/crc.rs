@@ -0,0 +1,99 @@
+/// Table of the CRCs of all 8-bit messages.
+const CRC_TABLE: [u32; 256] = make_crc_table();
+
+const fn make_crc_table() -> [u32; 256] {
+
+    let mut table = [0u32; 256];
+
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+
+    table
+}
+
+/// Computes the CRC of the bytes with the built-in table implementation.
+pub fn table_crc32(bytes: &[u8]) -> u32 {
+
+    let mut c = 0xffffffffu32;
+    for byte in bytes {
+        c = CRC_TABLE[((c ^ *byte as u32) & 0xff) as usize] ^ (c >> 8);
+    }
+
+    c ^ 0xffffffff
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Number of times [`crc32`] is called on the current thread,
+    /// which lets tests check that no CRC is computed.
+    pub(crate) static N_COMPUTED: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Computes the CRC of the bytes.
+/// See [CRC algorithm](http://www.libpng.org/pub/png/spec/1.2/PNG-Structure.html#CRC-algorithm).
+/// The `crc32fast` implementation is used when the `crc32fast` feature is enabled.
+#[cfg(feature = "crc32fast")]
+pub fn crc32(bytes: &[u8]) -> u32 {
+    #[cfg(test)]
+    N_COMPUTED.with(|n| n.set(n.get() + 1));
+
+    crc32fast::hash(bytes)
+}
+
+/// Computes the CRC of the bytes.
+/// See [CRC algorithm](http://www.libpng.org/pub/png/spec/1.2/PNG-Structure.html#CRC-algorithm).
+/// The built-in table implementation is used since the `crc32fast` feature is disabled.
+#[cfg(not(feature = "crc32fast"))]
+pub fn crc32(bytes: &[u8]) -> u32 {
+    #[cfg(test)]
+    N_COMPUTED.with(|n| n.set(n.get() + 1));
+
+    table_crc32(bytes)
+}
+
+/// Formats the CRC as a `0x`-prefixed 8-digit hex string, e.g., `0xabd1d84e`.
+pub fn format_crc(crc: u32) -> String {
+    format!("{:#010x}", crc)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_crc() {
+        assert_eq!(format_crc(2882656334), "0xabd1d84e");
+        assert_eq!(format_crc(0x1234), "0x00001234");
+    }
+
+    #[test]
+    fn test_table_crc32() {
+        assert_eq!(table_crc32(b""), 0);
+        assert_eq!(table_crc32(b"IEND"), 0xae426082);
+        assert_eq!(table_crc32(b"abc"), 0x352441c2);
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        assert_eq!(crc32(&bytes), table_crc32(&bytes));
+
+        #[cfg(feature = "crc32fast")]
+        assert_eq!(crc32fast::hash(&bytes), table_crc32(&bytes));
+    }
+}
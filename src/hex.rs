@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+use crate::Result;
+
+/// Encodes the bytes as a lowercase hex string.
+pub fn encode(bytes: &[u8]) -> String {
+    bytes.iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Decodes the hex string into bytes.
+/// Both uppercase and lowercase hex digits are accepted.
+pub fn decode(s: &str) -> Result<Vec<u8>> {
+
+    if !s.len().is_multiple_of(2) {
+        return Err(Box::new(HexError::OddLength(s.len())));
+    }
+
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| Ok(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect()
+}
+
+/// Converts a hex digit to its value.
+fn digit(c: u8) -> Result<u8> {
+    (c as char).to_digit(16)
+        .map(|d| d as u8)
+        .ok_or_else(|| Box::new(HexError::InvalidCharacter(c as char)).into())
+}
+
+#[derive(Debug)]
+pub enum HexError {
+    /// A hex string must have an even length.
+    OddLength(usize),
+
+    /// Every character must be a hex digit.
+    InvalidCharacter(char)
+}
+
+impl std::error::Error for HexError {}
+
+impl Display for HexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OddLength(length) => {
+                write!(f, "The hex string must have an even length while it has length {}", length)
+            },
+            Self::InvalidCharacter(c) => {
+                write!(f, "The hex string contains an invalid character {:?}", c)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("deadBEEF").unwrap(), vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(decode("").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode(&encode(&bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_invalid_hex() {
+        assert!(decode("abc").is_err());
+        assert!(decode("zz").is_err());
+        assert!(decode("é0").is_err());
+    }
+}
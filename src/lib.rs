@@ -1,7 +1,66 @@
 pub mod chunk;
+pub mod crc;
 pub mod chunk_type;
 pub mod png;
+pub mod hex;
+pub mod base64;
 pub mod cli;
+mod atomic;
 
 pub type Error = Box<dyn std::error::Error>;
 pub type Result<T> = std::result::Result<T, Error>;
+
+/// Parses a PNG from arbitrary input bytes.
+/// This function never panics, and malformed input only results in an error,
+/// which makes it suitable as a fuzzing entry point.
+pub fn parse_png(data: &[u8]) -> Result<png::Png> {
+    png::Png::try_from(data)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use png::Png;
+
+    #[test]
+    fn test_parse_malformed_png() {
+        let header = Png::STANDARD_HEADER.to_vec();
+        let with = |bytes: &[u8]| -> Vec<u8> {
+            header.iter().chain(bytes.iter()).copied().collect()
+        };
+
+        let corpus: Vec<Vec<u8>> = vec![
+            vec![],
+            vec![137, 80, 78],
+            vec![0; 8],
+            with(&[0, 0, 0]),
+            with(&[0, 0, 0, 0, 73, 69, 78]),
+            with(&[0xff, 0xff, 0xff, 0xff, 73, 69, 78, 68, 0, 0, 0, 0]),
+            with(&[0, 0, 0, 1, 73, 69, 78, 68, 0, 0, 0, 0]),
+            with(&[0, 0, 0, 0, 1, 2, 3, 4, 0, 0, 0, 0]),
+            with(&[0, 0, 0, 0, 73, 69, 78, 68, 0, 0, 0, 0]),
+        ];
+
+        for data in corpus {
+            assert!(parse_png(&data).is_err(), "{:?} should be rejected", data);
+        }
+    }
+
+    #[test]
+    fn test_parse_random_bytes() {
+        // a simple linear congruential generator to avoid extra dependencies
+        let mut state: u32 = 42;
+        let mut next_byte = || {
+            state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+            (state >> 24) as u8
+        };
+
+        for length in 0..512 {
+            let mut data: Vec<u8> = Png::STANDARD_HEADER.to_vec();
+            data.extend((0..length).map(|_| next_byte()));
+            let _ = parse_png(&data);
+            let _ = parse_png(&data[8..]);
+        }
+    }
+}
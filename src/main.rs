@@ -1,18 +1,26 @@
 
+use std::process::ExitCode;
 use clap::Parser;
 use colored::Colorize;
-use hackpng::Result;
-use hackpng::cli::Cli;
+use hackpng::cli::{self, Cli};
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
 
     let cli = Cli::parse();
 
-    cli.run().unwrap_or_else(
-        |e| 
-        eprintln!("{}", e.to_string().bright_red())
-    );
-    
-    Ok(())
+    // logs are written to stderr, separately from the user-facing output,
+    // and the level can be further configured by RUST_LOG
+    env_logger::Builder::new()
+        .filter_level(cli.log_level())
+        .parse_default_env()
+        .init();
+
+    match cli.run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e.to_string().bright_red());
+            ExitCode::from(cli::exit_code(&e))
+        }
+    }
 }
 
@@ -1,11 +1,210 @@
-use std::fmt::Display;
-
-use crate::{Result, Error};
+pub mod apng;
+pub mod builder;
+pub mod color;
+pub mod container;
+pub mod filebox;
+pub mod filter;
+pub mod ihdr;
+pub mod misc;
+pub mod plte;
+pub mod repair;
+pub mod tag;
+pub mod text;
+pub mod zlib;
+
+use std::{
+    str::FromStr,
+    fmt::Display,
+    io::{Read, Write, ErrorKind},
+    path::{Path, PathBuf}
+};
+
+use crate::{Result, Error, atomic};
 use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use apng::AnimationControl;
+use color::{Gamma, Srgb, Chromaticities};
+use ihdr::Ihdr;
+use misc::{SignificantBits, Background, PhysicalDimensions};
+use plte::Palette;
+
+/// Type code of the image header chunk, which must be the first chunk.
+pub const IHDR: &str = "IHDR";
+
+/// Type code of the palette chunk.
+pub const PLTE: &str = "PLTE";
+
+/// Type code of the image data chunk.
+pub const IDAT: &str = "IDAT";
+
+/// Type code of the image trailer chunk, which must be the last chunk.
+pub const IEND: &str = "IEND";
+
+/// Type code of the EXIF metadata chunk.
+pub const EXIF: &str = "eXIf";
+
+/// Type codes registered by the PNG specification and its extensions,
+/// which viewers may interpret according to their definitions.
+pub const STANDARD_CHUNK_TYPES: [&str; 33] = [
+    IHDR, PLTE, IDAT, IEND,
+    "tRNS", color::CHRM, color::GAMA, "iCCP", misc::SBIT, color::SRGB, "cICP", "mDCV", "cLLI",
+    text::TEXT, text::ZTXT, text::ITXT, misc::BKGD, "hIST", misc::PHYS, "sPLT", EXIF, "tIME",
+    apng::ACTL, apng::FCTL, apng::FDAT,
+    "oFFs", "pCAL", "sCAL", "gIFg", "gIFx", "gIFt", "sTER", "dSIG"
+];
+
+/// Types of the chunks which must not appear more than once.
+const SINGULAR_CHUNK_TYPES: [&str; 15] = [
+    IHDR, PLTE, IEND,
+    color::CHRM, color::GAMA, "iCCP", misc::SBIT, color::SRGB, misc::BKGD,
+    "hIST", "tRNS", misc::PHYS, "tIME", EXIF, apng::ACTL
+];
+
+/// Checks if the type code is registered, see [`STANDARD_CHUNK_TYPES`].
+pub fn is_standard_chunk_type(chunk_type: &str) -> bool {
+    STANDARD_CHUNK_TYPES.contains(&chunk_type)
+}
+
+/// Suggests the standard type code that the unrecognized type code is probably a typo of,
+/// e.g., `IDAT` for `IDTA`.
+/// Public type codes, whose second letter is uppercase, must be registered,
+/// so they are matched within an edit distance of 2 ignoring case,
+/// while private ones are only matched if they differ in case.
+pub fn suggest_standard_chunk_type(chunk_type: &str) -> Option<&'static str> {
+
+    if is_standard_chunk_type(chunk_type) {
+        return None;
+    }
+
+    let is_public = chunk_type.as_bytes().get(1).is_some_and(u8::is_ascii_uppercase);
+    let max_distance = if is_public { 2 } else { 0 };
+
+    let chunk_type = chunk_type.to_ascii_lowercase();
+    STANDARD_CHUNK_TYPES.iter()
+        .map(|standard_type| (edit_distance(&chunk_type, &standard_type.to_ascii_lowercase()), *standard_type))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, standard_type)| standard_type)
+}
+
+/// Levenshtein distance between the byte strings,
+/// i.e., the number of insertions, deletions and substitutions to turn one into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+
+    // distances from the prefixes of a to the current prefix of b
+    let mut distances: Vec<usize> = (0..=a.len()).collect();
+
+    for (j, b_byte) in b.bytes().enumerate() {
+        let mut diagonal = distances[0];
+        distances[0] = j + 1;
+        for (i, a_byte) in a.bytes().enumerate() {
+            let substitution = diagonal + (a_byte != b_byte) as usize;
+            diagonal = distances[i + 1];
+            distances[i + 1] = substitution
+                .min(distances[i] + 1)
+                .min(diagonal + 1);
+        }
+    }
+
+    distances[a.len()]
+}
+
+/// Appends a hint of the standard type code to an error message
+/// if the type code looks like a typo of it.
+pub(crate) fn did_you_mean(chunk_type: &str) -> String {
+    suggest_standard_chunk_type(chunk_type)
+        .map(|suggestion| format!(", did you mean {}?", suggestion))
+        .unwrap_or_default()
+}
+
+/// Totals of the chunk data lengths in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataSizes {
+    /// Sum of the data lengths of all chunks.
+    pub total: usize,
+
+    /// Sum of the data lengths of the critical chunks.
+    pub critical: usize,
+
+    /// Sum of the data lengths of the ancillary chunks.
+    pub ancillary: usize
+}
+
+impl DataSizes {
+
+    /// Ratio of the ancillary (metadata) bytes to the critical (image) bytes.
+    pub fn metadata_ratio(&self) -> f64 {
+        if self.critical == 0 {
+            return 0.0;
+        }
+        self.ancillary as f64 / self.critical as f64
+    }
+
+}
+
+/// Options of [`Png::try_from_opts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Whether the CRC of each chunk is verified, which is on by default.
+    /// Turning it off skips computing the CRCs, which speeds up parsing trusted files.
+    pub verify_crc: bool
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions { verify_crc: true }
+    }
+}
+
+/// The first byte at which re-serializing a parsed [`Png`] differs from the original file,
+/// together with the reason, as returned by [`Png::roundtrip_mismatch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundtripMismatch {
+    /// The stored CRC of the chunk at the `index` is wrong and a recomputed one is written instead.
+    CrcMismatch { offset: usize, index: usize },
+
+    /// Another byte of the chunk at the `index` is not reproduced, e.g., a non-canonical length field.
+    ChunkBytes { offset: usize, index: usize },
+
+    /// The bytes after the last chunk are not reproduced.
+    TrailingData { offset: usize }
+}
+
+impl Display for RoundtripMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CrcMismatch { offset, index } => {
+                write!(f, "the CRC of chunk {} at byte offset {} does not match its data", index, offset)
+            },
+            Self::ChunkBytes { offset, index } => {
+                write!(f, "chunk {} differs at byte offset {}", index, offset)
+            },
+            Self::TrailingData { offset } => {
+                write!(f, "the trailing bytes differ at byte offset {}", offset)
+            }
+        }
+    }
+}
+
+/// How the CRCs are handled when parsing.
+#[derive(Debug, Clone, Copy)]
+enum CrcCheck {
+    /// A CRC mismatch fails the parsing.
+    Verify,
+
+    /// Chunks with a mismatched CRC are kept and their indices are recorded.
+    Record,
+
+    /// The CRCs are not computed at all.
+    Skip
+}
 
 #[derive(Debug)]
 pub struct Png {
-    chunks: Vec<Chunk>
+    chunks: Vec<Chunk>,
+
+    /// Bytes after the `IEND` chunk that do not form a chunk.
+    trailing_data: Vec<u8>
 }
 
 impl Png {
@@ -14,17 +213,168 @@ impl Png {
     pub const STANDARD_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
 
     pub fn from_chunks(chunks: Vec<Chunk>) -> Self {
-        Png { chunks }
+        Png {
+            chunks,
+            trailing_data: vec![]
+        }
+    }
+
+    /// Creates an empty `Png` with room for at least `capacity` chunks,
+    /// so that appending that many chunks does not reallocate.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from_chunks(Vec::with_capacity(capacity))
+    }
+
+    /// Reserves room for at least `additional` more chunks.
+    pub fn reserve(&mut self, additional: usize) {
+        self.chunks.reserve(additional)
+    }
+
+    /// The number of chunks this `Png` can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.chunks.capacity()
+    }
+
+    /// Creates a minimal valid PNG of the given size,
+    /// which is an all-black 8-bit grayscale image.
+    pub fn new_blank(width: u32, height: u32) -> Result<Self> {
+
+        let ihdr = Ihdr::grayscale(width, height)?;
+
+        // each scanline starts with a filter type byte followed by one byte per pixel,
+        // and all of the image data must fit into a single IDAT chunk
+        let image_data_length = (width as usize).checked_add(1)
+            .and_then(|scanline_length| scanline_length.checked_mul(height as usize))
+            .filter(|length| zlib::stored_len(*length).is_some_and(|length| length <= Chunk::MAX_LENGTH))
+            .ok_or_else(|| Box::new(PngError::ImageTooLarge { width, height }))?;
+        let image_data = vec![0; image_data_length];
+
+        Ok(Self::from_chunks(vec![
+            ihdr.to_chunk(),
+            Chunk::new(ChunkType::from_str(IDAT)?, zlib::compress_stored(&image_data)),
+            Chunk::new(ChunkType::from_str(IEND)?, vec![])
+        ]))
     }
 
     /// Appends a chunk to the end of this `Png` file's `Chunk` list.
     pub fn append_chunk(&mut self, chunk: Chunk) {
+        log::debug!("Appended chunk {} at index {}", chunk.chunk_type(), self.chunks.len());
         self.chunks.push(chunk)
     }
 
     /// Inserts a chunk to the specified index of `Png` file's `Chunk` list.
-    pub fn insert_chunk(&mut self, index: usize, chunk: Chunk) {
+    /// Fails if the index is greater than the number of chunks.
+    pub fn insert_chunk(&mut self, index: usize, chunk: Chunk) -> Result<()> {
+
+        if index > self.chunks.len() {
+            return Err(Box::new(PngError::IndexOutOfBounds(index)));
+        }
+
+        log::debug!("Inserted chunk {} at index {}", chunk.chunk_type(), index);
         self.chunks.insert(index, chunk);
+
+        Ok(())
+    }
+
+    /// Appends the chunks in order to the end of this `Png` file's `Chunk` list.
+    pub fn append_chunks(&mut self, chunks: impl IntoIterator<Item = Chunk>) {
+        self.chunks.extend(chunks)
+    }
+
+    /// Inserts the chunks in order at the specified index of `Png` file's `Chunk` list.
+    /// Fails if the index is greater than the number of chunks.
+    pub fn insert_chunks(&mut self, index: usize, chunks: impl IntoIterator<Item = Chunk>) -> Result<()> {
+
+        if index > self.chunks.len() {
+            return Err(Box::new(PngError::IndexOutOfBounds(index)));
+        }
+
+        self.chunks.splice(index..index, chunks);
+
+        Ok(())
+    }
+
+    /// Replaces all chunks at once after checking that they form a valid PNG,
+    /// i.e., `IHDR` comes first, `IEND` comes last and no singular chunk is duplicated.
+    /// The chunks are left unchanged if the check fails.
+    pub fn set_chunks(&mut self, chunks: Vec<Chunk>) -> Result<()> {
+
+        let png = Png::from_chunks(chunks);
+        png.validate_structure()?;
+        match png.chunks.last() {
+            Some(chunk) if chunk.type_str() != Some(IEND) => {
+                return Err(Box::new(PngError::IendNotLast(chunk.chunk_type().to_string())));
+            },
+            _ => {}
+        }
+        png.validate_uniqueness()?;
+
+        self.chunks = png.chunks;
+
+        Ok(())
+    }
+
+    /// Appends a `tEXt` chunk with the keyword and the Latin-1 text before `IEND`,
+    /// where the keyword must follow the constraints of the PNG specification.
+    pub fn append_text(&mut self, keyword: &str, text: &str) -> Result<()> {
+
+        let chunk = text::TextChunk::new(keyword, text)?.to_chunk()?;
+
+        self.insert_chunk(self.index_before_iend(), chunk)
+    }
+
+    /// Copies the ancillary chunks of the other PNG into this one in their order
+    /// and returns the number of copied chunks.
+    /// The critical chunks of the other PNG are skipped,
+    /// and so are the chunks which may appear only once (e.g., `gAMA`) if this PNG already has one.
+    /// Chunks required to come before `PLTE` or `IDAT` are inserted accordingly,
+    /// and the others are inserted before `IEND` if present, otherwise appended.
+    /// Fails without any change if the resulting chunk layout is invalid, see [`Png::set_chunks`].
+    pub fn merge_ancillary_from(&mut self, other: &Png) -> Result<usize> {
+
+        let mut merged = Png::from_chunks(self.chunks.clone());
+        let mut n_copied = 0;
+        for chunk in other.iter_ancillary() {
+
+            let chunk_type = chunk.type_str().unwrap_or_default();
+            if SINGULAR_CHUNK_TYPES.contains(&chunk_type) && merged.contains_type(chunk_type) {
+                log::debug!("Skipped chunk {} which is already present", chunk.chunk_type());
+                continue;
+            }
+
+            let index = merged.merge_index(chunk_type);
+            merged.chunks.insert(index, chunk.clone());
+            n_copied += 1;
+        }
+
+        self.set_chunks(merged.chunks)?;
+
+        Ok(n_copied)
+    }
+
+    /// Index where an ancillary chunk of the type is merged,
+    /// following the ordering constraints of the PNG specification.
+    fn merge_index(&self, chunk_type: &str) -> usize {
+
+        let first_index_of = |types: &[&str]| self.find_index(|chunk| {
+            chunk.type_str().is_some_and(|chunk_type| types.contains(&chunk_type))
+        });
+
+        // ranks as in `Png::ordering_rank`
+        let index = match Self::ordering_rank(chunk_type, false) {
+            1 => first_index_of(&[PLTE, IDAT]),
+            3 | 4 => first_index_of(&[IDAT]),
+            _ => None
+        };
+
+        index.unwrap_or_else(|| self.index_before_iend())
+    }
+
+    /// Index where a chunk is inserted to come right before `IEND`,
+    /// which is the end of the chunks if there is no `IEND`.
+    fn index_before_iend(&self) -> usize {
+        self.find_index(|chunk| chunk.type_str() == Some(IEND))
+            .unwrap_or(self.chunks.len())
     }
 
     /// Searches for a `Chunk` with the specified `chunk_type` and removes the first
@@ -32,9 +382,9 @@ impl Png {
     pub fn remove_chunk(&mut self, chunk_type: &str) -> Result<Chunk> {
 
         // find the index fo the chunk to remove
-        let index_of_chunk_to_remove = self.chunks
-            .iter()
-            .position(|chunk| chunk.chunk_type().to_string().eq(chunk_type));
+        let index_of_chunk_to_remove = self.find_index(
+            |chunk| chunk.type_str() == Some(chunk_type)
+        );
 
         match index_of_chunk_to_remove {
             Some(index) => {
@@ -44,6 +394,7 @@ impl Png {
 
                 // remove the chunk
                 self.chunks.remove(index);
+                log::debug!("Removed chunk {} at index {}", chunk_type, index);
 
                 Ok(chunk)
             },
@@ -53,6 +404,72 @@ impl Png {
         }
     }
 
+    /// Removes the `Chunk` at the specified index and returns it.
+    /// The `IHDR` and `IEND` chunks are protected and cannot be removed.
+    pub fn remove_chunk_at(&mut self, index: usize) -> Result<Chunk> {
+
+        let chunk = self.chunks.get(index)
+            .ok_or_else(|| Box::new(PngError::IndexOutOfBounds(index)))?;
+
+        // the image cannot be read without the header and the trailer
+        let chunk_type = chunk.chunk_type().to_string();
+        if chunk_type == IHDR || chunk_type == IEND {
+            return Err(Box::new(PngError::CriticalChunk(chunk_type)));
+        }
+
+        let chunk = self.chunks.remove(index);
+        log::debug!("Removed chunk {} at index {}", chunk_type, index);
+
+        Ok(chunk)
+    }
+
+    /// Changes the type of the first `Chunk` with the type `old` to `new`,
+    /// keeping its data. Critical chunks cannot be retyped,
+    /// and neither can a chunk be retyped into a critical one.
+    pub fn retype_chunk(&mut self, old: &str, new: &str) -> Result<()> {
+
+        let new_chunk_type = ChunkType::from_str(new)?;
+
+        // find the chunk to retype
+        let index = self.find_index(|chunk| chunk.type_str() == Some(old))
+            .ok_or_else(|| Box::new(PngError::ChunkNotFound(old.to_string())))?;
+
+        // critical chunks are required to display the image,
+        // and a decoder would fail on an unknown critical chunk
+        if self.chunks[index].chunk_type().is_critical() {
+            return Err(Box::new(PngError::CriticalChunk(old.to_string())));
+        }
+        if new_chunk_type.is_critical() {
+            return Err(Box::new(PngError::CriticalChunkType(new.to_string())));
+        }
+
+        // change the type while keeping the data
+        self.chunks[index].set_chunk_type(new_chunk_type);
+
+        Ok(())
+    }
+
+    /// Replaces the data of the first `Chunk` with the type `chunk_type` by zeros
+    /// of the same length, so that the chunk stays in place without revealing its content.
+    /// Critical chunks cannot be redacted.
+    pub fn redact_chunk(&mut self, chunk_type: &str) -> Result<()> {
+
+        // find the chunk to redact
+        let chunk = self.chunk_by_type_mut(chunk_type)
+            .ok_or_else(|| Box::new(PngError::ChunkNotFound(chunk_type.to_string())))?;
+
+        // critical chunks are required to display the image
+        if chunk.chunk_type().is_critical() {
+            return Err(Box::new(PngError::CriticalChunk(chunk_type.to_string())));
+        }
+
+        // the CRC is computed from the new data
+        let length = chunk.data_len();
+        chunk.set_data(vec![0; length]);
+
+        Ok(())
+    }
+
     /// The header of this PNG.
     pub fn header(&self) -> &[u8; 8] {
         &Self::STANDARD_HEADER
@@ -63,243 +480,1767 @@ impl Png {
         &self.chunks
     }
 
-    /// Searches for a `Chunk` with the specified `chunk_type` and returns the first
-    /// matching `Chunk` from this `Png`.
-    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&Chunk> {
+    /// Consumes this `Png` and returns its `Chunk`s,
+    /// dropping the signature and any trailing data.
+    pub fn into_chunks(self) -> Vec<Chunk> {
+        self.chunks
+    }
 
-        // find the index fo the chunk
-        let index = self.chunks
+    /// Pairs each `Chunk` with the byte offset where it starts in the serialized file,
+    /// where the first chunk starts right after the 8-byte header.
+    pub fn chunk_offsets(&self) -> Vec<(usize, &Chunk)> {
+        let mut offset = Self::STANDARD_HEADER.len();
+        self.chunks
             .iter()
-            .position(|chunk| chunk.chunk_type().to_string().eq(chunk_type));
+            .map(|chunk| {
+                let chunk_offset = offset;
+                offset += Chunk::OVERHEAD + chunk.data_len();
+                (chunk_offset, chunk)
+            })
+            .collect()
+    }
 
-        // get the chunk by index
-        match index {
-            Some(index) => Some(&self.chunks[index]),
-            None => None
-        }
-        
+    /// Iterates over all chunks in order, allowing each to be modified in place.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Chunk> {
+        self.chunks.iter_mut()
     }
 
-    /// Returns this `Png` as a byte sequence.
-    /// These bytes will contain the header followed by the bytes of all of the chunks.
-    pub fn as_bytes(&self) -> Vec<u8> {
+    /// Iterates over the critical chunks in order.
+    pub fn iter_critical(&self) -> impl Iterator<Item = &Chunk> {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.chunk_type().is_critical())
+    }
 
-        // bytes of all chunks
-        let mut chunk_bytes: Vec<u8> = vec![];
+    /// Iterates over the ancillary chunks in order.
+    pub fn iter_ancillary(&self) -> impl Iterator<Item = &Chunk> {
+        self.chunks
+            .iter()
+            .filter(|chunk| !chunk.chunk_type().is_critical())
+    }
 
-        // collect the bytes of each chunk
-        for chunk in &self.chunks {
-            chunk_bytes.extend(chunk.as_bytes())
-        }
+    /// Finds the chunks that are likely to hide a message, ranked from the most likely.
+    ///
+    /// Only ancillary, private chunks with non-empty data are candidates, since no standard chunk
+    /// has this combination of properties. The candidates are ranked by the fraction of
+    /// printable bytes in their data (see [`Chunk::printable_ratio`]),
+    /// and then by the data length, so that longer texts come first.
+    /// Chunks that rank equally keep their order in the file.
+    pub fn find_message_chunks(&self) -> Vec<&Chunk> {
 
-        // bytes of the entire PNG
-        self.header()
+        let mut candidates: Vec<(f64, &Chunk)> = self.chunks
             .iter()
-            .chain(chunk_bytes.iter())
-            .copied()
-            .collect()
-    }
-}
+            .filter(|chunk| {
+                let chunk_type = chunk.chunk_type();
+                !chunk_type.is_critical() && !chunk_type.is_public() && chunk.data_len() > 0
+            })
+            .map(|chunk| (chunk.printable_ratio(), chunk))
+            .collect();
 
-impl TryFrom<&[u8]> for Png {
+        candidates.sort_by(|(ratio_a, chunk_a), (ratio_b, chunk_b)| {
+            ratio_b.total_cmp(ratio_a)
+                .then_with(|| chunk_b.data_len().cmp(&chunk_a.data_len()))
+        });
 
-    type Error = Error;
+        candidates.into_iter().map(|(_, chunk)| chunk).collect()
+    }
 
-    fn try_from(value: &[u8]) -> std::result::Result<Self, Self::Error> {
+    /// Returns the `Chunk` at the specified index, or `None` if the index is out of bounds.
+    pub fn chunk_at(&self, index: usize) -> Option<&Chunk> {
+        self.chunks.get(index)
+    }
 
-        // convert to a mutable slice
-        let mut bytes = value;
+    /// The number of chunks stored in this `Png`.
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
 
-        // extract header
-        let header: [u8; 8] = bytes[0..8].try_into().unwrap();
+    /// Checks if this `Png` has no chunks.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
 
-        // get the remaining bytes
-        bytes = &bytes[8..];
+    /// Bytes after the `IEND` chunk that do not form a chunk.
+    pub fn trailing_data(&self) -> &[u8] {
+        &self.trailing_data
+    }
 
-        // check header
-        if header != Self::STANDARD_HEADER {
-            return Err(Box::new(PngError::InvalidHeader));
+    /// Removes everything after the `IEND` chunk and returns the removed bytes,
+    /// including the bytes of any chunks after `IEND`.
+    /// If there is no `IEND` chunk, only the trailing data is removed.
+    pub fn remove_trailing_data(&mut self) -> Vec<u8> {
+
+        // remove the chunks after IEND
+        let mut removed_bytes: Vec<u8> = vec![];
+        if let Some(index) = self.find_index(|chunk| chunk.type_str() == Some(IEND)) {
+            for chunk in self.chunks.drain(index + 1..) {
+                removed_bytes.extend(chunk.as_bytes());
+            }
         }
 
-        // get all chunks
-        let mut chunks: Vec<Chunk> = vec![];
-        while bytes.len() > 0 {
+        // remove the trailing data
+        removed_bytes.append(&mut self.trailing_data);
 
-            // extract a chunk
-            let chunk = Chunk::try_from(bytes).unwrap();
+        removed_bytes
+    }
 
-            // get the remaining bytes
-            bytes = &bytes[chunk.as_bytes().len()..];
+    /// Sums up the data lengths of the chunks.
+    pub fn data_sizes(&self) -> DataSizes {
 
-            // collect the chunk
-            chunks.push(chunk)
+        let mut data_sizes = DataSizes {
+            total: 0,
+            critical: 0,
+            ancillary: 0
+        };
+
+        for chunk in &self.chunks {
+            let length = chunk.data_len();
+            data_sizes.total += length;
+            if chunk.chunk_type().is_critical() {
+                data_sizes.critical += length;
+            } else {
+                data_sizes.ancillary += length;
+            }
         }
 
-        Ok(Self::from_chunks(chunks))
+        data_sizes
     }
 
-}
+    /// Checks the Adler-32 checksum at the end of the zlib stream of the image data,
+    /// which verifies the image data beyond the CRCs of the chunks.
+    pub fn verify_idat_adler32(&self) -> Result<()> {
+        zlib::verify_adler32(&self.idat_data())
+    }
 
-impl Display for Png {
+    /// Concatenates the data of all `IDAT` chunks in order,
+    /// which forms the zlib stream of the image data.
+    /// Returns an empty vector if there are no `IDAT` chunks.
+    pub fn idat_data(&self) -> Vec<u8> {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.type_str() == Some(IDAT))
+            .flat_map(|chunk| chunk.data().iter())
+            .copied()
+            .collect()
+    }
 
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.chunks)
+    /// Total data length of all `IDAT` chunks.
+    pub fn total_idat_len(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.type_str() == Some(IDAT))
+            .map(|chunk| chunk.data_len())
+            .sum()
     }
 
-}
+    /// Searches for a `Chunk` with the specified `chunk_type` and returns a mutable reference
+    /// to the first matching `Chunk` from this `Png`.
+    pub fn chunk_by_type_mut(&mut self, chunk_type: &str) -> Option<&mut Chunk> {
+        self.chunks
+            .iter_mut()
+            .find(|chunk| chunk.type_str() == Some(chunk_type))
+    }
 
-#[derive(Debug)]
-pub enum PngError {
-    UnableToRemoveChunk,
-    InvalidHeader
-}
+    /// Returns the index of the first `Chunk` satisfying the predicate.
+    pub fn find_index<F: Fn(&Chunk) -> bool>(&self, pred: F) -> Option<usize> {
+        self.chunks.iter().position(pred)
+    }
 
-impl std::error::Error for PngError {}
+    /// Searches for a `Chunk` with the specified `chunk_type` and returns the first
+    /// matching `Chunk` from this `Png`.
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&Chunk> {
 
-impl Display for PngError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::UnableToRemoveChunk => {
-                write!(f, "{}", "Unable to remove the chunk")
-            },
-            Self::InvalidHeader => {
-                write!(f, "{}", "The extracted PNG header from the input data is invalid")
-            }
+        // find the index fo the chunk
+        let index = self.find_index(
+            |chunk| chunk.type_str() == Some(chunk_type)
+        );
+
+        // get the chunk by index
+        match index {
+            Some(index) => Some(&self.chunks[index]),
+            None => None
         }
+        
     }
-}
 
+    /// Checks if this `Png` has a `Chunk` with the specified `chunk_type`.
+    /// Returns `false` if `chunk_type` is not a valid chunk type,
+    /// even if a malformed chunk with this type has been read leniently.
+    pub fn contains_type(&self, chunk_type: &str) -> bool {
+        ChunkType::from_str(chunk_type).is_ok()
+            && self.chunks.iter().any(|chunk| chunk.type_str() == Some(chunk_type))
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::chunk_type::ChunkType;
-    use crate::chunk::Chunk;
-    use std::str::FromStr;
-    use std::convert::TryFrom;
+    /// Iterates over the chunks with the specified `chunk_type` in order.
+    pub fn chunks_by_type<'a>(&'a self, chunk_type: &'a str) -> impl Iterator<Item = &'a Chunk> {
+        self.chunks
+            .iter()
+            .filter(move |chunk| chunk.type_str() == Some(chunk_type))
+    }
 
-    fn testing_chunks() -> Vec<Chunk> {
-        let mut chunks = Vec::new();
+    /// Parses the `acTL` chunk if this `Png` is animated.
+    pub fn animation_control(&self) -> Option<Result<AnimationControl>> {
+        self.chunk_by_type(apng::ACTL).map(AnimationControl::try_from)
+    }
 
-        chunks.push(chunk_from_strings("FrSt", "I am the first chunk").unwrap());
-        chunks.push(chunk_from_strings("miDl", "I am another chunk").unwrap());
-        chunks.push(chunk_from_strings("LASt", "I am the last chunk").unwrap());
+    /// Parses the `PLTE` chunk if this `Png` has a palette.
+    pub fn palette(&self) -> Option<Result<Palette>> {
+        self.chunk_by_type(PLTE).map(Palette::try_from)
+    }
 
-        chunks
+    /// Parses the `gAMA` chunk if present.
+    pub fn gamma(&self) -> Option<Result<Gamma>> {
+        self.chunk_by_type(color::GAMA).map(Gamma::try_from)
     }
 
-    fn testing_png() -> Png {
-        let chunks = testing_chunks();
-        Png::from_chunks(chunks)
+    /// Checks if the image is Adam7-interlaced according to the `IHDR` chunk,
+    /// which fails if the `IHDR` chunk is missing or invalid.
+    pub fn is_interlaced(&self) -> Result<bool> {
+        let ihdr = self.chunk_by_type(IHDR)
+            .ok_or_else(|| Box::new(PngError::ChunkNotFound(IHDR.to_string())))?;
+        Ok(Ihdr::try_from(ihdr)?.is_interlaced())
     }
 
-    fn chunk_from_strings(chunk_type: &str, data: &str) -> Result<Chunk> {
+    /// Parses the `sRGB` chunk if present.
+    pub fn srgb(&self) -> Option<Result<Srgb>> {
+        self.chunk_by_type(color::SRGB).map(Srgb::try_from)
+    }
 
-        let chunk_type = ChunkType::from_str(chunk_type)?;
-        let data: Vec<u8> = data.bytes().collect();
+    /// Parses the `cHRM` chunk if present.
+    pub fn chromaticities(&self) -> Option<Result<Chromaticities>> {
+        self.chunk_by_type(color::CHRM).map(Chromaticities::try_from)
+    }
 
-        Ok(Chunk::new(chunk_type, data))
+    /// Parses the `sBIT` chunk if present.
+    pub fn significant_bits(&self) -> Option<Result<SignificantBits>> {
+        self.chunk_by_type(misc::SBIT).map(SignificantBits::try_from)
     }
 
-    #[test]
-    fn test_from_chunks() {
-        let chunks = testing_chunks();
-        let png = Png::from_chunks(chunks);
+    /// Parses the `bKGD` chunk if present.
+    pub fn background(&self) -> Option<Result<Background>> {
+        self.chunk_by_type(misc::BKGD).map(Background::try_from)
+    }
 
-        assert_eq!(png.chunks().len(), 3);
+    /// Parses the `pHYs` chunk if present.
+    pub fn physical_dimensions(&self) -> Option<Result<PhysicalDimensions>> {
+        self.chunk_by_type(misc::PHYS).map(PhysicalDimensions::try_from)
     }
 
-    #[test]
-    fn test_valid_from_bytes() {
-        let chunk_bytes: Vec<u8> = testing_chunks()
-            .into_iter()
-            .flat_map(|chunk| chunk.as_bytes())
-            .collect();
+    /// The raw EXIF data, i.e., a TIFF blob, stored in the `eXIf` chunk if present.
+    pub fn exif(&self) -> Option<&[u8]> {
+        self.chunk_by_type(EXIF).map(|chunk| chunk.data())
+    }
 
-        let bytes: Vec<u8> = Png::STANDARD_HEADER
-            .iter()
-            .chain(chunk_bytes.iter())
-            .copied()
-            .collect();
+    /// Checks that the chunk types which may appear at most once
+    /// (e.g., `IHDR`, `PLTE`, `IEND`, `gAMA`) are not duplicated.
+    pub fn validate_uniqueness(&self) -> Result<()> {
+
+        for chunk_type in SINGULAR_CHUNK_TYPES {
+            let count = self.chunks
+                .iter()
+                .filter(|chunk| chunk.type_str() == Some(chunk_type))
+                .count();
+            if count > 1 {
+                return Err(Box::new(PngError::DuplicateChunk(chunk_type.to_string())));
+            }
+        }
 
-        let png = Png::try_from(bytes.as_ref());
+        Ok(())
+    }
+
+    /// Checks the chunk layout required by the PNG specification,
+    /// i.e., the first chunk must be `IHDR`.
+    pub fn validate_structure(&self) -> Result<()> {
+
+        // IHDR must come first
+        match self.chunks.first() {
+            None => {
+                return Err(Box::new(PngError::ChunkNotFound(IHDR.to_string())));
+            },
+            Some(chunk) if chunk.type_str() != Some(IHDR) => {
+                return Err(Box::new(PngError::IhdrNotFirst(chunk.chunk_type().to_string())));
+            },
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Finds cheap structural anomalies which the parser does not reject,
+    /// i.e., a missing `IDAT` chunk and an `IEND` chunk with data.
+    pub fn anomalies(&self) -> Vec<PngError> {
+
+        let mut anomalies: Vec<PngError> = vec![];
+
+        // there must be image data
+        if self.chunks_by_type(IDAT).next().is_none() {
+            anomalies.push(PngError::ChunkNotFound(IDAT.to_string()));
+        }
+
+        // the trailer must be empty
+        if let Some(iend) = self.chunk_by_type(IEND) {
+            if iend.length() > 0 {
+                anomalies.push(PngError::NonEmptyIend(iend.length()));
+            }
+        }
+
+        anomalies
+    }
+
+    /// Checks if the chunk layout follows the PNG specification.
+    /// See [`Png::validate_structure`] for the checks.
+    pub fn is_structurally_valid(&self) -> bool {
+        self.validate_structure().is_ok()
+    }
+
+    /// Reorders the chunks to follow the ordering rules of the PNG specification:
+    /// `IHDR` comes first and `IEND` last, `PLTE` comes before `IDAT`,
+    /// `IDAT` chunks are consecutive, and ancillary chunks with ordering constraints
+    /// (e.g., `gAMA` before `PLTE`, `tRNS` between `PLTE` and `IDAT`) are moved accordingly.
+    /// Chunks without constraints stay on the same side of the image data,
+    /// and the relative order within each group, including the `IDAT` sequence, is preserved.
+    /// Fails without any change if the reordered layout is still invalid,
+    /// e.g., a singular chunk appears twice, see [`Png::set_chunks`].
+    pub fn normalize_ordering(&mut self) -> Result<()> {
+
+        // position of the first IDAT chunk
+        let first_idat_index = self.find_index(|chunk| chunk.type_str() == Some(IDAT))
+            .unwrap_or(self.chunks.len());
+
+        // rank each chunk
+        let mut ranked_chunks: Vec<(u8, Chunk)> = self.chunks
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(index, chunk)| {
+                let rank = Self::ordering_rank(chunk.type_str().unwrap_or_default(), index < first_idat_index);
+                (rank, chunk)
+            })
+            .collect();
+
+        // the sort is stable so that the relative order is preserved
+        ranked_chunks.sort_by_key(|(rank, _)| *rank);
+
+        self.set_chunks(ranked_chunks.into_iter()
+            .map(|(_, chunk)| chunk)
+            .collect())
+    }
+
+    /// Sorts the ancillary chunks by their type codes and then by their data,
+    /// so that the same set of chunks always gives the same bytes.
+    /// The critical chunks (`IHDR`, `PLTE`, `IDAT`, `IEND`, etc.) and the APNG frame chunks
+    /// (`fcTL` and `fdAT`) keep their positions,
+    /// and only the chunks within each contiguous run of the other ancillary chunks are rearranged,
+    /// so that no chunk crosses a critical chunk, e.g., moves from before `IDAT` to after it,
+    /// and the sequence of animation frames is preserved.
+    /// Fails without any change if the layout is invalid, see [`Png::set_chunks`].
+    pub fn sort_ancillary(&mut self) -> Result<()> {
+
+        // chunks whose order carries meaning
+        let is_fixed = |chunk: &Chunk| {
+            chunk.chunk_type().is_critical()
+                || matches!(chunk.type_str(), Some(apng::FCTL) | Some(apng::FDAT))
+        };
+
+        let mut chunks = self.chunks.clone();
+        let runs = chunks.chunk_by_mut(|a, b| is_fixed(a) == is_fixed(b));
+
+        for run in runs.filter(|run| !is_fixed(&run[0])) {
+            run.sort_by(|a, b| {
+                a.chunk_type().bytes().cmp(&b.chunk_type().bytes())
+                    .then_with(|| a.data().cmp(b.data()))
+            });
+        }
+
+        self.set_chunks(chunks)
+    }
+
+    /// Rank of the chunk type for the ordering, where lower ranks come first.
+    fn ordering_rank(chunk_type: &str, is_before_idat: bool) -> u8 {
+        match chunk_type {
+            IHDR => 0,
+            "cHRM" | "gAMA" | "iCCP" | "sBIT" | "sRGB" => 1,
+            PLTE => 2,
+            "bKGD" | "hIST" | "tRNS" => 3,
+            "pHYs" | "sPLT" | apng::ACTL => 4,
+            IDAT => 5,
+            IEND => 7,
+            _ if is_before_idat => 4,
+            _ => 6
+        }
+    }
+
+    /// Returns this `Png` as a byte sequence.
+    /// These bytes will contain the header followed by the bytes of all of the chunks.
+    pub fn as_bytes(&self) -> Vec<u8> {
+
+        // bytes of all chunks
+        let mut chunk_bytes: Vec<u8> = vec![];
+
+        // collect the bytes of each chunk
+        for chunk in &self.chunks {
+            chunk_bytes.extend(chunk.as_bytes())
+        }
+
+        // bytes of the entire PNG
+        self.header()
+            .iter()
+            .chain(chunk_bytes.iter())
+            .chain(self.trailing_data.iter())
+            .copied()
+            .collect()
+    }
+
+    /// Returns this `Png` as a byte sequence like [`Png::as_bytes`],
+    /// which fails if the data of any chunk is longer than [`Chunk::MAX_LENGTH`].
+    pub fn try_as_bytes(&self) -> Result<Vec<u8>> {
+        for chunk in &self.chunks {
+            chunk.try_length()?;
+        }
+        Ok(self.as_bytes())
+    }
+
+    /// The number of bytes of this `Png` as returned by [`Png::as_bytes`],
+    /// which is computed without serializing the chunks.
+    pub fn byte_len(&self) -> usize {
+        self.header().len()
+            + self.chunks.iter().map(|chunk| Chunk::OVERHEAD + chunk.data_len()).sum::<usize>()
+            + self.trailing_data.len()
+    }
+
+    /// Returns the header followed by the bytes of the critical chunks only,
+    /// i.e., ignoring all ancillary chunks.
+    /// This byte sequence does not change when only the metadata is modified.
+    pub fn critical_bytes(&self) -> Vec<u8> {
+
+        // bytes of all critical chunks
+        let mut chunk_bytes: Vec<u8> = vec![];
+
+        // collect the bytes of each critical chunk
+        for chunk in self.iter_critical() {
+            chunk_bytes.extend(chunk.as_bytes())
+        }
+
+        self.header()
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect()
+    }
+
+    /// Checks if parsing the bytes and then serializing the `Png` again
+    /// reproduces exactly the same bytes.
+    pub fn is_byte_exact_roundtrip(bytes: &[u8]) -> bool {
+        match Self::try_from(bytes) {
+            Ok(png) => png.roundtrip_mismatch(bytes).is_none(),
+            Err(_) => false
+        }
+    }
+
+    /// Compares the serialized bytes of this `Png` with the `original` bytes it was parsed from,
+    /// and tells where and why they first differ, or `None` if they are identical.
+    pub fn roundtrip_mismatch(&self, original: &[u8]) -> Option<RoundtripMismatch> {
+
+        let serialized = self.as_bytes();
+
+        // if one is a prefix of the other, they differ where the shorter one ends
+        let offset = serialized.iter()
+            .zip(original)
+            .position(|(a, b)| a != b)
+            .or_else(|| (serialized.len() != original.len()).then(|| serialized.len().min(original.len())))?;
+
+        // find the chunk containing the offset
+        for (index, (chunk_offset, chunk)) in self.chunk_offsets().into_iter().enumerate() {
+            let crc_offset = chunk_offset + Chunk::OVERHEAD - 4 + chunk.data_len();
+            if offset >= crc_offset + 4 {
+                continue;
+            }
+
+            if offset >= crc_offset {
+                return Some(RoundtripMismatch::CrcMismatch { offset, index });
+            }
+            return Some(RoundtripMismatch::ChunkBytes { offset, index });
+        }
+
+        Some(RoundtripMismatch::TrailingData { offset })
+    }
+
+    /// Parses a PNG from the input bytes.
+    /// If `strict` is not set, chunks with invalid type codes are still accepted,
+    /// which is useful for reading unusual real-world files.
+    pub fn from_bytes(value: &[u8], strict: bool) -> Result<Self> {
+        Self::parse(value, strict, CrcCheck::Verify).map(|(png, _)| png)
+    }
+
+    /// Parses a PNG from the input bytes like `Png::try_from`,
+    /// except that chunks whose CRC does not match are kept instead of failing.
+    /// Returns the PNG together with the indices of such chunks.
+    pub fn from_bytes_tolerant(value: &[u8]) -> Result<(Self, Vec<usize>)> {
+        Self::parse(value, true, CrcCheck::Record)
+    }
+
+    /// Parses a PNG from the input bytes like `Png::try_from` with the given options,
+    /// e.g., skipping the CRC verification of trusted files for speed.
+    pub fn try_from_opts(value: &[u8], options: ParseOptions) -> Result<Self> {
+        let crc_check = if options.verify_crc {
+            CrcCheck::Verify
+        } else {
+            CrcCheck::Skip
+        };
+        Self::parse(value, true, crc_check).map(|(png, _)| png)
+    }
+
+    /// Parses a sequence of concatenated chunks without the PNG signature,
+    /// e.g., chunks exported by another tool, until the input is exhausted.
+    pub fn from_chunks_bytes(value: &[u8]) -> Result<Vec<Chunk>> {
+
+        let mut bytes = value;
+
+        // offset of the next chunk in the input bytes
+        let mut offset = 0;
+
+        let mut chunks: Vec<Chunk> = vec![];
+        while !bytes.is_empty() {
+
+            // extract a chunk
+            let chunk = Chunk::try_from(bytes)
+                .map_err(|source| PngError::ChunkParse { offset, source })?;
+
+            // get the remaining bytes
+            let chunk_length = Chunk::OVERHEAD + chunk.data_len();
+            bytes = &bytes[chunk_length..];
+            offset += chunk_length;
+
+            chunks.push(chunk)
+        }
+
+        Ok(chunks)
+    }
+
+    /// Parses a PNG from the input bytes, where the CRCs are handled according to `crc_check`.
+    /// Returns the PNG together with the indices of the chunks whose CRC does not match
+    /// if they are recorded.
+    fn parse(value: &[u8], strict: bool, crc_check: CrcCheck) -> Result<(Self, Vec<usize>)> {
+
+        // convert to a mutable slice
+        let mut bytes = value;
+
+        // the input must at least contain the header
+        if bytes.len() < 8 {
+            return Err(Box::new(PngError::InvalidHeader));
+        }
+
+        // extract header
+        let header: [u8; 8] = bytes[0..8].try_into()?;
+
+        // get the remaining bytes
+        bytes = &bytes[8..];
+
+        // check header
+        if header != Self::STANDARD_HEADER {
+            return Err(Box::new(PngError::InvalidHeader));
+        }
+
+        // offset of the next chunk in the input bytes
+        let mut offset = Self::STANDARD_HEADER.len();
+
+        // get all chunks
+        let mut chunks: Vec<Chunk> = vec![];
+        let mut trailing_data: Vec<u8> = vec![];
+        let mut crc_mismatches: Vec<usize> = vec![];
+        let mut seen_iend = false;
+        while !bytes.is_empty() {
+
+            // after IEND, bytes that do not form a chunk are trailing data
+            if seen_iend && !Self::starts_with_chunk(bytes, strict) {
+                trailing_data = bytes.to_vec();
+                break;
+            }
+
+            // extract a chunk
+            let chunk = match crc_check {
+                CrcCheck::Verify => Chunk::from_bytes(bytes, strict),
+                CrcCheck::Record => Chunk::from_bytes_unchecked_crc(bytes, strict).map(|(chunk, crc)| {
+                    if chunk.crc() != crc {
+                        log::debug!("Kept chunk {} with a mismatched CRC at byte offset {}", chunk.chunk_type(), offset);
+                        crc_mismatches.push(chunks.len());
+                    }
+                    chunk
+                }),
+                CrcCheck::Skip => Chunk::from_bytes_unchecked_crc(bytes, strict).map(|(chunk, _)| chunk)
+            }.map_err(|source| PngError::ChunkParse { offset, source })?;
+
+            log::trace!("Parsed chunk {} ({} bytes) at byte offset {}", chunk.chunk_type(), chunk.length(), offset);
+
+            // get the remaining bytes
+            let chunk_length = Chunk::OVERHEAD + chunk.data_len();
+            bytes = &bytes[chunk_length..];
+            offset += chunk_length;
+
+            // collect the chunk
+            seen_iend = seen_iend || chunk.chunk_type().bytes() == *b"IEND";
+            chunks.push(chunk)
+        }
+
+        Ok((
+            Png {
+                chunks,
+                trailing_data
+            },
+            crc_mismatches
+        ))
+    }
+
+    /// Reads and parses the PNG file at the path like `Png::try_from`.
+    /// Failures in reading the file are reported together with the path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::try_from(read_path(path)?.as_slice())
+    }
+
+    /// Writes this PNG into the file at the path.
+    /// The file is replaced atomically, i.e., it is never left half-written,
+    /// and failures are reported together with the path.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let bytes = self.try_as_bytes()?;
+        atomic::write_file(path, |f| f.write_all(&bytes))
+            .map_err(|source| Box::new(PngError::Io { path: path.to_path_buf(), source }).into())
+    }
+
+    /// Checks if the bytes start with a complete and valid chunk.
+    fn starts_with_chunk(bytes: &[u8], strict: bool) -> bool {
+        Chunk::from_bytes(bytes, strict).is_ok()
+    }
+
+    /// Reads the chunks one by one from the reader
+    /// and returns the first chunk of the given type without reading the rest.
+    /// The scan stops after `max_chunks` chunks if given.
+    /// Returns `None` if no chunk of the type is found.
+    pub fn scan_for_type<R: Read>(mut reader: R, chunk_type: &str, max_chunks: Option<usize>) -> Result<Option<Chunk>> {
+
+        // validate the chunk type
+        ChunkType::from_str(chunk_type)?;
+
+        // check header
+        let mut header = [0; 8];
+        reader.read_exact(&mut header)
+            .map_err(|_| Box::new(PngError::InvalidHeader))?;
+        if header != Self::STANDARD_HEADER {
+            return Err(Box::new(PngError::InvalidHeader));
+        }
+
+        // offset of the next chunk in the input
+        let mut offset = Self::STANDARD_HEADER.len();
+
+        let mut n_chunks = 0;
+        while max_chunks.is_none_or(|max_chunks| n_chunks < max_chunks) {
+
+            // read the length and the chunk type, stopping at the end of the input
+            let mut prefix = [0; 8];
+            match reader.read_exact(&mut prefix) {
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => {
+                    return Ok(None);
+                },
+                result => result?
+            }
+
+            // read the data and the CRC, where a truncated chunk fails to parse below
+            let data_length = u32::from_be_bytes(prefix[0..4].try_into()?) as u64;
+            let mut rest = vec![];
+            reader.by_ref()
+                .take(data_length + 4)
+                .read_to_end(&mut rest)?;
+
+            // extract the chunk
+            let bytes: Vec<u8> = prefix.iter()
+                .chain(rest.iter())
+                .copied()
+                .collect();
+            let chunk = Chunk::try_from(bytes.as_slice())
+                .map_err(|source| PngError::ChunkParse { offset, source })?;
+
+            if chunk.type_str() == Some(chunk_type) {
+                return Ok(Some(chunk));
+            }
+
+            n_chunks += 1;
+            offset += bytes.len();
+        }
+
+        Ok(None)
+    }
+}
+
+/// Reads the whole file at the path,
+/// where a failure is reported together with the path.
+pub fn read_path<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
+    let path = path.as_ref();
+    std::fs::read(path)
+        .map_err(|source| Box::new(PngError::Io { path: path.to_path_buf(), source }).into())
+}
+
+/// Encodes the message data into a new chunk of the given type.
+/// The chunk is inserted at `index` if given, otherwise it is appended.
+pub fn encode_message(png: &mut Png, chunk_type: &str, data: Vec<u8>, index: Option<usize>) -> Result<()> {
+
+    // create the chunk from the given chunk type and message
+    let chunk = Chunk::new(ChunkType::from_str(chunk_type)?, data);
+
+    // encode the message into PNG
+    match index {
+        Some(index) => {
+            png.insert_chunk(index, chunk)?;
+        },
+        None => {
+            png.append_chunk(chunk);
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces the data of the first chunk of the given type by the message data if it exists,
+/// otherwise encodes the message into a new chunk like [`encode_message`].
+/// Returns the index of the message chunk and whether an existing chunk is replaced.
+pub fn upsert_message(png: &mut Png, chunk_type: &str, data: Vec<u8>, index: Option<usize>) -> Result<(usize, bool)> {
+
+    // validate the chunk type
+    ChunkType::from_str(chunk_type)?;
+
+    // replace the existing message
+    if let Some(existing_index) = png.find_index(|chunk| chunk.type_str() == Some(chunk_type)) {
+        png.chunks[existing_index].set_data(data);
+        return Ok((existing_index, true));
+    }
+
+    encode_message(png, chunk_type, data, index)?;
+
+    Ok((index.unwrap_or(png.len() - 1), false))
+}
+
+/// Decodes the message data from the first chunk of the given type.
+/// Returns `None` if there is no such chunk.
+pub fn decode_message(png: &Png, chunk_type: &str) -> Result<Option<Vec<u8>>> {
+
+    // validate the chunk type
+    ChunkType::from_str(chunk_type)?;
+
+    Ok(png.chunk_by_type(chunk_type).map(|chunk| chunk.data().to_vec()))
+}
+
+/// Decodes the message data split across all chunks of the given type
+/// by concatenating their data in order.
+/// Returns `None` if there is no such chunk.
+pub fn decode_joined_message(png: &Png, chunk_type: &str) -> Result<Option<Vec<u8>>> {
+
+    // validate the chunk type
+    ChunkType::from_str(chunk_type)?;
+
+    // chunks holding the parts of the message
+    let chunks: Vec<&Chunk> = png.chunks_by_type(chunk_type).collect();
+
+    if chunks.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        chunks.iter()
+            .flat_map(|chunk| chunk.data().iter())
+            .copied()
+            .collect()
+    ))
+}
+
+/// Decodes the message data from the chunk at `index` among the chunks of the given type,
+/// where the index is 0-based.
+/// Returns `None` if there is no such chunk, and fails if the index is out of range.
+pub fn decode_selected_message(png: &Png, chunk_type: &str, index: usize) -> Result<Option<Vec<u8>>> {
+
+    // validate the chunk type
+    ChunkType::from_str(chunk_type)?;
+
+    // chunks of the type
+    let chunks: Vec<&Chunk> = png.chunks_by_type(chunk_type).collect();
+
+    if chunks.is_empty() {
+        return Ok(None);
+    }
+
+    match chunks.get(index) {
+        Some(chunk) => Ok(Some(chunk.data().to_vec())),
+        None => Err(Box::new(PngError::SelectionOutOfRange {
+            chunk_type: chunk_type.to_string(),
+            index,
+            count: chunks.len()
+        }))
+    }
+}
+
+impl TryFrom<&[u8]> for Png {
+
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> std::result::Result<Self, Self::Error> {
+        Self::from_bytes(value, true)
+    }
+
+}
+
+impl IntoIterator for Png {
+
+    type Item = Chunk;
+    type IntoIter = std::vec::IntoIter<Chunk>;
+
+    /// Iterates over the owned chunks like [`Png::into_chunks`].
+    fn into_iter(self) -> Self::IntoIter {
+        self.chunks.into_iter()
+    }
+
+}
+
+impl Display for Png {
+
+    /// Summarizes the structure, i.e., the signature, the chunk count,
+    /// and the type and data length of each chunk on its own line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+
+        // the signature is checked when parsing, so it is always the standard one
+        write!(f, "PNG (standard signature, {} chunks)", self.chunks.len())?;
+
+        for (index, chunk) in self.chunks.iter().enumerate() {
+            write!(f, "\n{:>4}  {}  {:>10} bytes", index, chunk.chunk_type(), chunk.length())?;
+        }
+
+        if !self.trailing_data.is_empty() {
+            write!(f, "\n{} trailing bytes", self.trailing_data.len())?;
+        }
+
+        Ok(())
+    }
+
+}
+
+#[derive(Debug)]
+pub enum PngError {
+    UnableToRemoveChunk,
+    InvalidHeader,
+    ChunkNotFound(String),
+    CriticalChunk(String),
+
+    /// The contained chunk type is critical, which a modified chunk must not have.
+    CriticalChunkType(String),
+    IndexOutOfBounds(usize),
+    DuplicateChunk(String),
+
+    /// The first chunk is not `IHDR` but of the contained type.
+    IhdrNotFirst(String),
+
+    /// The last chunk is not `IEND` but of the contained type.
+    IendNotLast(String),
+
+    /// The `IEND` chunk has the contained data length, which must be zero.
+    NonEmptyIend(u32),
+
+    /// The selected index is not less than the `count` of chunks of the type.
+    SelectionOutOfRange { chunk_type: String, index: usize, count: usize },
+
+    /// The image data of a blank image of the size does not fit into a single chunk.
+    ImageTooLarge { width: u32, height: u32 },
+
+    /// The chunk starting at the byte `offset` of the input cannot be parsed.
+    ChunkParse { offset: usize, source: Error },
+
+    /// The file at the `path` cannot be accessed.
+    Io { path: PathBuf, source: std::io::Error }
+}
+
+impl std::error::Error for PngError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ChunkParse { source, .. } => Some(source.as_ref()),
+            Self::Io { source, .. } => Some(source),
+            _ => None
+        }
+    }
+}
+
+impl Display for PngError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnableToRemoveChunk => {
+                write!(f, "Unable to remove the chunk")
+            },
+            Self::InvalidHeader => {
+                write!(f, "The extracted PNG header from the input data is invalid")
+            },
+            Self::ChunkNotFound(chunk_type) => {
+                write!(f, "No chunk of type {} is found{}", chunk_type, did_you_mean(chunk_type))
+            },
+            Self::CriticalChunk(chunk_type) => {
+                write!(f, "The chunk of type {} is critical and cannot be modified", chunk_type)
+            },
+            Self::CriticalChunkType(chunk_type) => {
+                write!(f, "The chunk type {} is critical, which decoders would fail to handle", chunk_type)
+            },
+            Self::IendNotLast(chunk_type) => {
+                write!(f, "The last chunk must be IEND but it is {}", chunk_type)
+            },
+            Self::IndexOutOfBounds(index) => {
+                write!(f, "The chunk index {} is out of bounds", index)
+            },
+            Self::DuplicateChunk(chunk_type) => {
+                write!(f, "The chunk of type {} must not appear more than once", chunk_type)
+            },
+            Self::IhdrNotFirst(chunk_type) => {
+                write!(f, "The first chunk must be {} but it is {}", IHDR, chunk_type)
+            },
+            Self::NonEmptyIend(length) => {
+                write!(f, "The {} chunk must be empty but it has {} bytes", IEND, length)
+            },
+            Self::SelectionOutOfRange { chunk_type, index, count } => {
+                write!(f, "Cannot select the chunk {} of type {} since there are only {} of them", index, chunk_type, count)
+            },
+            Self::ImageTooLarge { width, height } => {
+                write!(f, "The image data of a {}x{} image does not fit into a single {} chunk", width, height, IDAT)
+            },
+            Self::ChunkParse { offset, source } => {
+                write!(f, "Failed in parsing the chunk at byte offset {} ({:#x}): {}", offset, offset, source)
+            },
+            Self::Io { path, source } => {
+                write!(f, "Failed in accessing {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use crate::chunk::Chunk;
+    use std::str::FromStr;
+    use std::convert::TryFrom;
+
+    fn testing_chunks() -> Vec<Chunk> {
+        vec![
+            chunk_from_strings("FrSt", "I am the first chunk").unwrap(),
+            chunk_from_strings("miDl", "I am another chunk").unwrap(),
+            chunk_from_strings("LASt", "I am the last chunk").unwrap(),
+        ]
+    }
+
+    fn testing_png() -> Png {
+        let chunks = testing_chunks();
+        Png::from_chunks(chunks)
+    }
+
+    fn chunk_from_strings(chunk_type: &str, data: &str) -> Result<Chunk> {
+
+        let chunk_type = ChunkType::from_str(chunk_type)?;
+
+        Ok(Chunk::from_text(chunk_type, data))
+    }
+
+    #[test]
+    fn test_from_chunks() {
+        let chunks = testing_chunks();
+        let png = Png::from_chunks(chunks);
+
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_valid_from_bytes() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = Png::STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png = Png::try_from(bytes.as_ref());
+
+        assert!(png.is_ok());
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = [13, 80, 78, 71, 13, 10, 26, 10]
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png = Png::try_from(bytes.as_ref());
+
+        assert!(png.is_err());
+    }
+
+    #[test]
+    fn test_invalid_chunk() {
+        let mut chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        #[rustfmt::skip]
+        let mut bad_chunk = vec![
+            0, 0, 0, 5,         // length
+            32, 117, 83, 116,   // Chunk Type (bad)
+            65, 64, 65, 66, 67, // Data
+            1, 2, 3, 4, 5       // CRC (bad)
+        ];
+
+        chunk_bytes.append(&mut bad_chunk);
+
+        let png = Png::try_from(chunk_bytes.as_ref());
+
+        assert!(png.is_err());
+    }
+
+    #[test]
+    fn test_chunk_parse_error_offset() {
+        // corrupt the data of the sRGB chunk following the 25-byte IHDR chunk
+        let mut bytes = PNG_FILE.to_vec();
+        bytes[8 + 25 + 8] ^= 0xff;
+
+        let error = Png::try_from(bytes.as_ref()).unwrap_err();
+        assert!(error.to_string().contains("offset 33"));
+        match error.downcast_ref::<PngError>() {
+            Some(PngError::ChunkParse { offset, .. }) => assert_eq!(*offset, 33),
+            _ => panic!("expected a chunk parse error")
+        }
+    }
+
+
+    #[test]
+    fn test_from_bytes_tolerant() {
+        let png = testing_png();
+        let mut bytes = png.as_bytes();
+
+        // corrupt the CRC of the second chunk
+        let (offset, chunk) = png.chunk_offsets()[1];
+        let crc_index = offset + chunk.as_bytes().len() - 1;
+        bytes[crc_index] ^= 0xff;
+
+        assert!(Png::try_from(bytes.as_slice()).is_err());
+        let (tolerant, crc_mismatches) = Png::from_bytes_tolerant(&bytes).unwrap();
+        assert_eq!(crc_mismatches, [1]);
+        assert_eq!(tolerant.len(), 3);
+        assert_eq!(&tolerant.chunk_at(1).unwrap().data_as_string().unwrap(), "I am another chunk");
+
+        let (_, crc_mismatches) = Png::from_bytes_tolerant(&png.as_bytes()).unwrap();
+        assert!(crc_mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_lenient_from_bytes() {
+        let chunk_type = ChunkType::from_bytes_lenient([82, 117, 49, 116]);
+        let mut png = testing_png();
+        png.append_chunk(Chunk::from_text(chunk_type, "Message"));
+        let bytes = png.as_bytes();
+
+        assert!(Png::try_from(bytes.as_ref()).is_err());
+
+        let png = Png::from_bytes(bytes.as_ref(), false).unwrap();
+        assert_eq!(png.chunks().len(), 4);
+        assert_eq!(png.as_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_lenient_non_utf8_chunk_type() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.insert_chunk(1, Chunk::from_text(ChunkType::from_bytes_lenient([b'R', 0xff, b'S', b't']), "Message")).unwrap();
+        let mut bytes = png.as_bytes();
+        bytes.extend(b"junk");
+
+        let png = Png::from_bytes(&bytes, false).unwrap();
+        assert_eq!(png.chunk_at(1).unwrap().chunk_type().to_string(), "R\\xffSt");
+        assert_eq!(png.trailing_data(), b"junk");
+
+        // formatting does not panic
+        assert!(png.to_string().contains("R\\xffSt"));
+    }
+
+    #[test]
+    fn test_list_chunks() {
+        let png = testing_png();
+        let chunks = png.chunks();
+        assert_eq!(chunks.len(), 3);
+    }
+
+    #[test]
+    fn test_chunk_at() {
+        let png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let chunk = png.chunk_at(0).unwrap();
+        assert_eq!(&chunk.chunk_type().to_string(), "IHDR");
+        assert!(png.chunk_at(png.len()).is_none());
+    }
+
+    #[test]
+    fn test_find_index() {
+        let png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let index = png.find_index(|chunk| !chunk.chunk_type().is_critical());
+        assert_eq!(index, Some(1));
+        assert_eq!(&png.chunk_at(1).unwrap().chunk_type().to_string(), "sRGB");
+
+        let index = png.find_index(|chunk| chunk.length() > 1_000_000);
+        assert!(index.is_none());
+    }
+
+    #[test]
+    fn test_chunk_by_type() {
+        let png = testing_png();
+        let chunk = png.chunk_by_type("FrSt").unwrap();
+        assert_eq!(&chunk.chunk_type().to_string(), "FrSt");
+        assert_eq!(&chunk.data_as_string().unwrap(), "I am the first chunk");
+
+    }
+
+    /// Logger collecting the messages of the records for testing.
+    struct TestLogger {
+        messages: std::sync::Mutex<Vec<String>>
+    }
+
+    impl log::Log for TestLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            self.messages.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    static TEST_LOGGER: TestLogger = TestLogger { messages: std::sync::Mutex::new(vec![]) };
+
+    #[test]
+    fn test_trace_logs() {
+        log::set_logger(&TEST_LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let mut png = Png::try_from(testing_png().as_bytes().as_slice()).unwrap();
+        png.remove_chunk("miDl").unwrap();
+
+        let messages = TEST_LOGGER.messages.lock().unwrap();
+        assert!(messages.iter().any(|message| message.starts_with("Parsed chunk FrSt")));
+        assert!(messages.iter().any(|message| message.starts_with("Removed chunk miDl")));
+    }
+
+    #[test]
+    fn test_remove_chunk_at() {
+        let mut png = testing_png();
+        let chunk = png.remove_chunk_at(1).unwrap();
+        assert_eq!(&chunk.chunk_type().to_string(), "miDl");
+        assert_eq!(png.len(), 2);
+        assert_eq!(&png.chunk_at(1).unwrap().chunk_type().to_string(), "LASt");
+
+        assert!(png.remove_chunk_at(2).is_err());
+    }
+
+    #[test]
+    fn test_remove_protected_chunk_at() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        let result = png.remove_chunk_at(0);
+        assert!(result.unwrap_err().to_string().contains("IHDR"));
+        assert!(png.remove_chunk_at(2).is_err());
+        assert!(png.remove_chunk_at(1).is_ok());
+        assert_eq!(png.len(), 2);
+    }
+
+    #[test]
+    fn test_chunk_offsets() {
+        let png = testing_png();
+        let offsets: Vec<usize> = png.chunk_offsets()
+            .iter()
+            .map(|(offset, _)| *offset)
+            .collect();
+
+        // each chunk has 12 bytes besides its data
+        assert_eq!(offsets, [8, 8 + 12 + 20, 8 + 12 + 20 + 12 + 18]);
+        assert_eq!(png.chunk_offsets()[1].1.chunk_type().to_string(), "miDl");
+    }
+
+    #[test]
+    fn test_chunks_by_type() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("FrSt", "I am another first chunk").unwrap());
+        let data: Vec<String> = png.chunks_by_type("FrSt")
+            .map(|chunk| chunk.data_as_string().unwrap())
+            .collect();
+        assert_eq!(data, ["I am the first chunk", "I am another first chunk"]);
+        assert_eq!(png.chunks_by_type("TeSt").count(), 0);
+    }
 
-        assert!(png.is_ok());
+    #[test]
+    fn test_chunk_by_type_mut() {
+        let mut png = testing_png();
+        png.chunk_by_type_mut("miDl").unwrap().set_data(b"I am a modified chunk".to_vec());
+        assert!(png.chunk_by_type_mut("TeSt").is_none());
+
+        let png = Png::try_from(png.as_bytes().as_ref()).unwrap();
+        let chunk = png.chunk_by_type("miDl").unwrap();
+        assert_eq!(&chunk.data_as_string().unwrap(), "I am a modified chunk");
     }
 
     #[test]
-    fn test_invalid_header() {
-        let chunk_bytes: Vec<u8> = testing_chunks()
-            .into_iter()
-            .flat_map(|chunk| chunk.as_bytes())
+    fn test_append_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message").unwrap());
+        let chunk = png.chunk_by_type("TeSt").unwrap();
+        assert_eq!(&chunk.chunk_type().to_string(), "TeSt");
+        assert_eq!(&chunk.data_as_string().unwrap(), "Message");
+    }
+
+    #[test]
+    fn test_append_chunks() {
+        let mut png = testing_png();
+        png.append_chunks(vec![
+            chunk_from_strings("TeSt", "one").unwrap(),
+            chunk_from_strings("TeSt", "two").unwrap(),
+            chunk_from_strings("TeSt", "three").unwrap(),
+        ]);
+        let actual: Vec<String> = png.chunks()[3..]
+            .iter()
+            .map(|chunk| chunk.data_as_string().unwrap())
             .collect();
+        assert_eq!(png.len(), 6);
+        assert_eq!(actual, vec!["one", "two", "three"]);
+    }
 
-        let bytes: Vec<u8> = [13, 80, 78, 71, 13, 10, 26, 10]
+    #[test]
+    fn test_insert_chunks() {
+        let mut png = testing_png();
+        png.insert_chunks(1, vec![
+            chunk_from_strings("TeSt", "one").unwrap(),
+            chunk_from_strings("TeSt", "two").unwrap(),
+        ]).unwrap();
+        let actual: Vec<String> = png.chunks()
             .iter()
-            .chain(chunk_bytes.iter())
-            .copied()
+            .map(|chunk| chunk.chunk_type().to_string())
             .collect();
+        assert_eq!(actual, vec!["FrSt", "TeSt", "TeSt", "miDl", "LASt"]);
+        assert_eq!(&png.chunk_at(2).unwrap().data_as_string().unwrap(), "two");
+
+        // out of bounds
+        assert!(png.insert_chunks(7, vec![chunk_from_strings("TeSt", "three").unwrap()]).is_err());
+        assert!(png.insert_chunk(7, chunk_from_strings("TeSt", "three").unwrap()).is_err());
+        assert_eq!(png.len(), 5);
+        png.insert_chunk(5, chunk_from_strings("TeSt", "three").unwrap()).unwrap();
+        assert_eq!(png.len(), 6);
+    }
 
-        let png = Png::try_from(bytes.as_ref());
+    #[test]
+    fn test_remove_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message").unwrap());
+        png.remove_chunk("TeSt").unwrap();
+        let chunk = png.chunk_by_type("TeSt");
+        assert!(chunk.is_none());
+    }
 
-        assert!(png.is_err());
+    #[test]
+    fn test_retype_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("ruSt", "Message").unwrap());
+        png.retype_chunk("ruSt", "teSt").unwrap();
+        assert!(!png.contains_type("ruSt"));
+
+        let png = Png::try_from(png.as_bytes().as_ref()).unwrap();
+        let chunk = png.chunk_by_type("teSt").unwrap();
+        assert_eq!(&chunk.data_as_string().unwrap(), "Message");
     }
 
     #[test]
-    fn test_invalid_chunk() {
-        let mut chunk_bytes: Vec<u8> = testing_chunks()
-            .into_iter()
-            .flat_map(|chunk| chunk.as_bytes())
+    fn test_retype_critical_chunk() {
+        let mut png = testing_png();
+        assert!(png.retype_chunk("FrSt", "frSt").is_err());
+        assert!(png.retype_chunk("NoNe", "noNe").is_err());
+
+        // an ancillary chunk cannot become critical
+        png.append_chunk(chunk_from_strings("ruSt", "Message").unwrap());
+        assert!(png.retype_chunk("ruSt", "RUST").is_err());
+        assert!(png.contains_type("ruSt"));
+    }
+
+    #[test]
+    fn test_encode_message() {
+        let mut png = testing_png();
+        encode_message(&mut png, "ruSt", b"Appended".to_vec(), None).unwrap();
+        encode_message(&mut png, "teSt", b"Inserted".to_vec(), Some(1)).unwrap();
+        assert_eq!(png.len(), 5);
+        assert_eq!(&png.chunk_at(1).unwrap().data_as_string().unwrap(), "Inserted");
+        assert_eq!(&png.chunk_at(4).unwrap().data_as_string().unwrap(), "Appended");
+
+        assert!(encode_message(&mut png, "ruSt", vec![], Some(6)).is_err());
+        assert!(encode_message(&mut png, "ru1t", vec![], None).is_err());
+    }
+
+    #[test]
+    fn test_decode_message() {
+        let mut png = testing_png();
+        encode_message(&mut png, "ruSt", b"Message".to_vec(), None).unwrap();
+        assert_eq!(decode_message(&png, "ruSt").unwrap(), Some(b"Message".to_vec()));
+        assert_eq!(decode_message(&png, "teSt").unwrap(), None);
+        assert!(decode_message(&png, "ru1t").is_err());
+    }
+
+    #[test]
+    fn test_scan_for_type() {
+        // bytes after the second chunk do not form a chunk
+        let mut bytes = testing_png().as_bytes();
+        let n_bytes = 8 + testing_chunks()[..2].iter().map(|chunk| chunk.as_bytes().len()).sum::<usize>();
+        bytes.truncate(n_bytes);
+        bytes.extend([0xff; 16]);
+
+        let chunk = Png::scan_for_type(bytes.as_slice(), "miDl", None).unwrap().unwrap();
+        assert_eq!(&chunk.data_as_string().unwrap(), "I am another chunk");
+        assert!(Png::from_bytes(&bytes, true).is_err());
+
+        // the limit is reached before the target
+        assert!(Png::scan_for_type(bytes.as_slice(), "miDl", Some(1)).unwrap().is_none());
+
+        // the target does not exist
+        let bytes = testing_png().as_bytes();
+        assert!(Png::scan_for_type(bytes.as_slice(), "TeSt", None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_iter_critical_and_ancillary() {
+        let png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let critical: Vec<String> = png.iter_critical()
+            .map(|chunk| chunk.chunk_type().to_string())
             .collect();
+        assert_eq!(critical, vec!["IHDR", "IDAT", "RuSt", "IEND"]);
+        assert_eq!(png.iter_ancillary().count(), 3);
+        assert_eq!(png.iter_critical().count() + png.iter_ancillary().count(), png.len());
+    }
 
-        #[rustfmt::skip]
-        let mut bad_chunk = vec![
-            0, 0, 0, 5,         // length
-            32, 117, 83, 116,   // Chunk Type (bad)
-            65, 64, 65, 66, 67, // Data
-            1, 2, 3, 4, 5       // CRC (bad)
-        ];
+    #[test]
+    fn test_contains_type() {
+        let mut png = testing_png();
+        assert!(png.contains_type("miDl"));
+        assert!(!png.contains_type("ruSt"));
+        assert!(!png.contains_type("miD"));
 
-        chunk_bytes.append(&mut bad_chunk);
+        // a malformed type is never reported as present
+        png.append_chunk(Chunk::new(ChunkType::from_bytes_lenient(*b"ab1d"), vec![]));
+        assert!(!png.contains_type("ab1d"));
+    }
 
-        let png = Png::try_from(chunk_bytes.as_ref());
+    #[test]
+    fn test_find_message_chunks() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.insert_chunk(1, Chunk::new(ChunkType::from_str("biNa").unwrap(), vec![0x00, 0x9c, 0xff, 0x01, 0x80, 0x7f, b'a', 0x02])).unwrap();
+        png.insert_chunk(2, chunk_from_strings("flAg", "FLAG{planted}").unwrap()).unwrap();
+        png.insert_chunk(3, chunk_from_strings("shOr", "flag").unwrap()).unwrap();
+        png.insert_chunk(4, Chunk::new(ChunkType::from_str("emPt").unwrap(), vec![])).unwrap();
 
-        assert!(png.is_err());
+        // a public ancillary chunk is not a candidate even if printable
+        png.insert_chunk(5, chunk_from_strings("tEXt", "Comment\0public").unwrap()).unwrap();
+
+        let found: Vec<String> = png.find_message_chunks()
+            .iter()
+            .map(|chunk| chunk.chunk_type().to_string())
+            .collect();
+        assert_eq!(found, vec!["flAg", "shOr", "biNa"]);
     }
 
+    #[test]
+    fn test_palette() {
+        let mut png = testing_png();
+        assert!(png.palette().is_none());
+
+        png.insert_chunk(1, chunk_from_strings("PLTE", "abcdef").unwrap()).unwrap();
+        let palette = png.palette().unwrap().unwrap();
+        assert_eq!(palette.entries, vec![(b'a', b'b', b'c'), (b'd', b'e', b'f')]);
+    }
 
     #[test]
-    fn test_list_chunks() {
+    fn test_exif() {
+        let mut png = testing_png();
+        assert!(png.exif().is_none());
+
+        let exif_data = b"MM\0\x2a\0\0\0\x08\0\0".to_vec();
+        png.append_chunk(Chunk::new(ChunkType::from_str(EXIF).unwrap(), exif_data.clone()));
+        assert_eq!(png.exif(), Some(exif_data.as_slice()));
+    }
+
+    #[test]
+    fn test_decode_joined_message() {
+        let mut png = testing_png();
+        encode_message(&mut png, "ruSt", b"Hello, ".to_vec(), None).unwrap();
+        encode_message(&mut png, "ruSt", b"world!".to_vec(), None).unwrap();
+        assert_eq!(decode_joined_message(&png, "ruSt").unwrap(), Some(b"Hello, world!".to_vec()));
+        assert_eq!(decode_message(&png, "ruSt").unwrap(), Some(b"Hello, ".to_vec()));
+        assert_eq!(decode_joined_message(&png, "teSt").unwrap(), None);
+    }
+
+    #[test]
+    fn test_idat_data() {
+        let png = Png::from_chunks(vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("IDAT", "data 1").unwrap(),
+            chunk_from_strings("tEXt", "text").unwrap(),
+            chunk_from_strings("IDAT", "data 2").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+        ]);
+        assert_eq!(png.idat_data(), b"data 1data 2");
+        assert_eq!(png.total_idat_len(), 12);
+
         let png = testing_png();
-        let chunks = png.chunks();
-        assert_eq!(chunks.len(), 3);
+        assert!(png.idat_data().is_empty());
+        assert_eq!(png.total_idat_len(), 0);
     }
 
     #[test]
-    fn test_chunk_by_type() {
+    fn test_upsert_message() {
+        let mut png = testing_png();
+
+        assert_eq!(upsert_message(&mut png, "ruSt", b"first".to_vec(), None).unwrap(), (3, false));
+        assert_eq!(upsert_message(&mut png, "ruSt", b"second".to_vec(), Some(0)).unwrap(), (3, true));
+        assert_eq!(png.len(), 4);
+        assert_eq!(png.chunks_by_type("ruSt").count(), 1);
+        assert_eq!(decode_message(&png, "ruSt").unwrap(), Some(b"second".to_vec()));
+    }
+
+    #[test]
+    fn test_from_path() {
+        let png = Png::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/message.png")).unwrap();
+        assert_eq!(png.len(), 4);
+        assert_eq!(decode_message(&png, "ruSt").unwrap(), Some(b"Hello, fixture!".to_vec()));
+
+        let result = Png::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/missing.png"));
+        assert!(result.unwrap_err().to_string().contains("missing.png"));
+    }
+
+    #[test]
+    fn test_is_interlaced() {
+        let png = Png::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/interlaced.png")).unwrap();
+        assert!(png.is_interlaced().unwrap());
+
+        let png = Png::from_path(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/message.png")).unwrap();
+        assert!(!png.is_interlaced().unwrap());
+
+        assert!(testing_png().is_interlaced().is_err());
+    }
+
+    #[test]
+    fn test_save() {
+        let filepath = std::env::temp_dir().join("hackpng_test_save.png");
+        let mut png = Png::new_blank(2, 2).unwrap();
+        encode_message(&mut png, "ruSt", b"Message".to_vec(), Some(1)).unwrap();
+        png.save(&filepath).unwrap();
+
+        let saved = Png::from_path(&filepath).unwrap();
+        assert_eq!(saved.as_bytes(), png.as_bytes());
+        assert_eq!(decode_message(&saved, "ruSt").unwrap(), Some(b"Message".to_vec()));
+        std::fs::remove_file(&filepath).unwrap();
+
+        let result = png.save(std::env::temp_dir().join("hackpng_missing_dir").join("saved.png"));
+        assert!(result.unwrap_err().to_string().contains("hackpng_missing_dir"));
+    }
+
+    #[test]
+    fn test_try_from_opts() {
         let png = testing_png();
-        let chunk = png.chunk_by_type("FrSt").unwrap();
-        assert_eq!(&chunk.chunk_type().to_string(), "FrSt");
-        assert_eq!(&chunk.data_as_string().unwrap(), "I am the first chunk");
+        let mut bytes: Vec<u8> = png.header().to_vec();
+        bytes.extend(png.chunks()[0].as_bytes_with_crc(0));
+        for chunk in &png.chunks()[1..] {
+            bytes.extend(chunk.as_bytes());
+        }
+
+        assert!(Png::try_from_opts(&bytes, ParseOptions::default()).is_err());
 
+        // no CRC is computed when the verification is turned off
+        let n_computed = crate::crc::N_COMPUTED.with(|n| n.get());
+        let parsed = Png::try_from_opts(&bytes, ParseOptions { verify_crc: false }).unwrap();
+        assert_eq!(crate::crc::N_COMPUTED.with(|n| n.get()), n_computed);
+        assert_eq!(parsed.as_bytes(), png.as_bytes());
+
+        let parsed = Png::try_from_opts(&png.as_bytes(), ParseOptions::default()).unwrap();
+        assert_eq!(parsed.as_bytes(), png.as_bytes());
     }
 
     #[test]
-    fn test_append_chunk() {
-        let mut png = testing_png();
-        png.append_chunk(chunk_from_strings("TeSt", "Message").unwrap());
-        let chunk = png.chunk_by_type("TeSt").unwrap();
-        assert_eq!(&chunk.chunk_type().to_string(), "TeSt");
-        assert_eq!(&chunk.data_as_string().unwrap(), "Message");
+    fn test_from_chunks_bytes() {
+        let bytes: Vec<u8> = chunk_from_strings("ruSt", "first").unwrap().as_bytes().into_iter()
+            .chain(chunk_from_strings("teSt", "second").unwrap().as_bytes())
+            .collect();
+
+        let chunks = Png::from_chunks_bytes(&bytes).unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].chunk_type().to_string(), "ruSt");
+        assert_eq!(chunks[0].data(), b"first");
+        assert_eq!(chunks[1].chunk_type().to_string(), "teSt");
+        assert_eq!(chunks[1].data(), b"second");
+
+        assert!(Png::from_chunks_bytes(&[]).unwrap().is_empty());
+
+        // a truncated chunk is reported with its offset
+        let result = Png::from_chunks_bytes(&bytes[..bytes.len() - 1]);
+        assert!(result.unwrap_err().to_string().contains("byte offset 17"));
     }
 
     #[test]
-    fn test_remove_chunk() {
-        let mut png = testing_png();
-        png.append_chunk(chunk_from_strings("TeSt", "Message").unwrap());
-        png.remove_chunk("TeSt").unwrap();
-        let chunk = png.chunk_by_type("TeSt");
-        assert!(chunk.is_none());
+    fn test_anomalies() {
+        let png = Png::new_blank(1, 1).unwrap();
+        assert!(png.anomalies().is_empty());
+
+        let png = Png::from_chunks(vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("IEND", "data").unwrap(),
+        ]);
+        let anomalies = png.anomalies();
+        assert_eq!(anomalies.len(), 2);
+        assert!(matches!(&anomalies[0], PngError::ChunkNotFound(chunk_type) if chunk_type == IDAT));
+        assert!(matches!(anomalies[1], PngError::NonEmptyIend(4)));
+        assert_eq!(anomalies[1].to_string(), "The IEND chunk must be empty but it has 4 bytes");
+    }
+
+    #[test]
+    fn test_ihdr_not_first() {
+        let png = Png::from_chunks(vec![
+            chunk_from_strings("tEXt", "text").unwrap(),
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+        ]);
+        assert!(!png.is_structurally_valid());
+        match png.validate_structure().unwrap_err().downcast_ref::<PngError>() {
+            Some(PngError::IhdrNotFirst(chunk_type)) => assert_eq!(chunk_type, "tEXt"),
+            _ => panic!("expected IhdrNotFirst")
+        }
+
+        let png = Png::try_from(&PNG_FILE[..]).unwrap();
+        assert!(png.is_structurally_valid());
+        assert!(!Png::from_chunks(vec![]).is_structurally_valid());
+    }
+
+    #[test]
+    fn test_normalize_ordering() {
+        let mut png = Png::from_chunks(vec![
+            chunk_from_strings("gAMA", "gamma").unwrap(),
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("tEXt", "text").unwrap(),
+            chunk_from_strings("PLTE", "palette").unwrap(),
+            chunk_from_strings("IDAT", "data 1").unwrap(),
+            chunk_from_strings("tIME", "time").unwrap(),
+            chunk_from_strings("IDAT", "data 2").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+            chunk_from_strings("ruSt", "Message").unwrap(),
+        ]);
+        png.normalize_ordering().unwrap();
+
+        let actual: Vec<String> = png.chunks()
+            .iter()
+            .map(|chunk| chunk.data_as_string().unwrap())
+            .collect();
+        let expected = vec![
+            "header", "gamma", "palette", "text", "data 1", "data 2", "time", "Message", ""
+        ];
+        assert_eq!(actual, expected);
+
+        // a duplicate singular chunk cannot be fixed by reordering
+        let chunks = vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("IDAT", "data").unwrap(),
+            chunk_from_strings("gAMA", "gamma 1").unwrap(),
+            chunk_from_strings("gAMA", "gamma 2").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+        ];
+        let mut png = Png::from_chunks(chunks);
+        let bytes = png.as_bytes();
+        assert!(png.normalize_ordering().is_err());
+        assert_eq!(png.as_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_sort_ancillary() {
+        let mut png = Png::from_chunks(vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("tEXt", "b").unwrap(),
+            chunk_from_strings("gAMA", "gamma").unwrap(),
+            chunk_from_strings("IDAT", "data 1").unwrap(),
+            chunk_from_strings("tEXt", "a").unwrap(),
+            chunk_from_strings("IDAT", "data 2").unwrap(),
+            chunk_from_strings("ruSt", "Message").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+        ]);
+        png.sort_ancillary().unwrap();
+
+        let actual: Vec<String> = png.chunks()
+            .iter()
+            .map(|chunk| chunk.data_as_string().unwrap())
+            .collect();
+        let expected = vec![
+            "header", "gamma", "b", "data 1", "a", "data 2", "Message", ""
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_merge_ancillary_from() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.insert_chunk(1, chunk_from_strings("gAMA", "base").unwrap()).unwrap();
+
+        let mut other = Png::new_blank(2, 2).unwrap();
+        other.insert_chunks(1, vec![
+            chunk_from_strings("gAMA", "other").unwrap(),
+            chunk_from_strings("sRGB", "s").unwrap(),
+            chunk_from_strings("pHYs", "p").unwrap(),
+        ]).unwrap();
+        other.insert_chunk(5, chunk_from_strings("tEXt", "t").unwrap()).unwrap();
+
+        assert_eq!(png.merge_ancillary_from(&other).unwrap(), 3);
+        let actual: Vec<String> = png.chunks()
+            .iter()
+            .map(|chunk| chunk.chunk_type().to_string())
+            .collect();
+        assert_eq!(actual, vec!["IHDR", "gAMA", "sRGB", "pHYs", "IDAT", "tEXt", "IEND"]);
+        assert_eq!(png.chunk_by_type("gAMA").unwrap().data(), b"base");
+        assert!(png.validate_uniqueness().is_ok());
+
+        // an invalid result is rejected without any change
+        let mut png = Png::from_chunks(vec![chunk_from_strings("IDAT", "data").unwrap()]);
+        assert!(png.merge_ancillary_from(&other).is_err());
+        assert_eq!(png.len(), 1);
+    }
+
+    #[test]
+    fn test_sort_ancillary_keeps_sides_of_idat() {
+        let mut png = Png::from_chunks(vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            chunk_from_strings("sRGB", "s").unwrap(),
+            chunk_from_strings("gAMA", "g").unwrap(),
+            chunk_from_strings("IDAT", "data").unwrap(),
+            chunk_from_strings("tEXt", "t").unwrap(),
+            chunk_from_strings("ruSt", "r").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+        ]);
+        png.sort_ancillary().unwrap();
+
+        let actual: Vec<String> = png.chunks()
+            .iter()
+            .map(|chunk| chunk.chunk_type().to_string())
+            .collect();
+        assert_eq!(actual, vec!["IHDR", "gAMA", "sRGB", "IDAT", "ruSt", "tEXt", "IEND"]);
+
+        // the frames of an APNG stay in sequence
+        let mut png = Png::from_chunks(vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            Chunk::new(ChunkType::from_str("acTL").unwrap(), vec![0, 0, 0, 2, 0, 0, 0, 0]),
+            chunk_from_strings("fcTL", "frame 0").unwrap(),
+            chunk_from_strings("IDAT", "data 0").unwrap(),
+            chunk_from_strings("fcTL", "frame 1").unwrap(),
+            chunk_from_strings("fdAT", "data 1").unwrap(),
+            chunk_from_strings("tEXt", "t").unwrap(),
+            chunk_from_strings("fcTL", "frame 2").unwrap(),
+            chunk_from_strings("fdAT", "data 2").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+        ]);
+        let bytes = png.as_bytes();
+        png.sort_ancillary().unwrap();
+        assert_eq!(png.as_bytes(), bytes);
+
+        // a chunk after IEND is rejected instead of being sorted
+        png.append_chunk(chunk_from_strings("aaAa", "after").unwrap());
+        assert!(png.sort_ancillary().is_err());
+        assert_eq!(png.chunk_at(png.len() - 1).unwrap().type_str(), Some("aaAa"));
+    }
+
+    #[test]
+    fn test_set_chunks() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        let bytes = png.as_bytes();
+
+        // no IEND
+        let mut chunks = png.chunks().to_vec();
+        chunks.pop();
+        let result = png.set_chunks(chunks);
+        assert_eq!(result.unwrap_err().to_string(), "The last chunk must be IEND but it is IDAT");
+        assert_eq!(png.as_bytes(), bytes);
+
+        // duplicate IHDR
+        let mut chunks = png.chunks().to_vec();
+        chunks.insert(1, chunks[0].clone());
+        assert!(png.set_chunks(chunks).is_err());
+        assert!(png.set_chunks(vec![]).is_err());
+        assert_eq!(png.as_bytes(), bytes);
+
+        let mut chunks = png.chunks().to_vec();
+        chunks.insert(1, chunk_from_strings("ruSt", "Message").unwrap());
+        png.set_chunks(chunks).unwrap();
+        assert_eq!(png.len(), 4);
+        assert_eq!(png.chunk_at(1).unwrap().data(), b"Message");
+    }
+
+    #[test]
+    fn test_validate_uniqueness() {
+        let mut png = Png::try_from(&PNG_FILE[..]).unwrap();
+        assert!(png.validate_uniqueness().is_ok());
+
+        png.append_chunk(chunk_from_strings("tEXt", "text").unwrap());
+        png.append_chunk(chunk_from_strings("tEXt", "more text").unwrap());
+        assert!(png.validate_uniqueness().is_ok());
+
+        png.insert_chunk(1, png.chunk_at(0).unwrap().clone()).unwrap();
+        let result = png.validate_uniqueness();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("IHDR"));
+    }
+
+    #[test]
+    fn test_data_sizes() {
+        let png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let data_sizes = png.data_sizes();
+        assert_eq!(data_sizes.total, 4711);
+        assert_eq!(data_sizes.critical, 4697);
+        assert_eq!(data_sizes.ancillary, 14);
+        assert!((data_sizes.metadata_ratio() - 14.0 / 4697.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_verify_idat_adler32() {
+        let mut png = Png::new_blank(2, 2).unwrap();
+        png.verify_idat_adler32().unwrap();
+
+        let idat = png.chunk_by_type_mut(IDAT).unwrap();
+        let mut data = idat.data().to_vec();
+        *data.last_mut().unwrap() ^= 1;
+        idat.set_data(data);
+        assert!(png.verify_idat_adler32().is_err());
+    }
+
+    #[test]
+    fn test_new_blank() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        encode_message(&mut png, "ruSt", b"Message".to_vec(), Some(2)).unwrap();
+
+        let png = Png::try_from(png.as_bytes().as_ref()).unwrap();
+        let ihdr = Ihdr::try_from(png.chunk_at(0).unwrap()).unwrap();
+        assert_eq!((ihdr.width, ihdr.height), (1, 1));
+        assert_eq!(&png.chunk_at(1).unwrap().chunk_type().to_string(), "IDAT");
+        assert_eq!(&png.chunk_at(3).unwrap().chunk_type().to_string(), "IEND");
+        assert_eq!(decode_message(&png, "ruSt").unwrap(), Some(b"Message".to_vec()));
+
+        assert!(Png::new_blank(0, 1).is_err());
+
+        // too large dimensions are rejected instead of allocating
+        assert!(Png::new_blank(1 << 31, 1).is_err());
+        assert!(Png::new_blank(u32::MAX, u32::MAX).is_err());
+        assert!(Png::new_blank(Ihdr::MAX_DIMENSION, Ihdr::MAX_DIMENSION).is_err());
     }
 
     #[test]
@@ -316,6 +2257,140 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_byte_len() {
+        let png = Png::try_from(&PNG_FILE[..]).unwrap();
+        assert_eq!(png.byte_len(), PNG_FILE.len());
+
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("ruSt", "Message").unwrap());
+        assert_eq!(png.byte_len(), png.as_bytes().len());
+    }
+
+    #[test]
+    fn test_critical_bytes() {
+        let mut png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let critical_bytes = png.critical_bytes();
+        png.insert_chunk(1, chunk_from_strings("tEXt", "Comment\0Hello").unwrap()).unwrap();
+        assert_eq!(png.critical_bytes(), critical_bytes);
+        assert!(critical_bytes.len() < PNG_FILE.len());
+    }
+
+    #[test]
+    fn test_animation_control() {
+        let png = Png::try_from(&PNG_FILE[..]).unwrap();
+        assert!(png.animation_control().is_none());
+
+        let png = Png::from_chunks(vec![
+            chunk_from_strings("IHDR", "header").unwrap(),
+            Chunk::new(ChunkType::from_str("acTL").unwrap(), vec![0, 0, 0, 2, 0, 0, 0, 0]),
+            chunk_from_strings("fcTL", "frame 0").unwrap(),
+            chunk_from_strings("IDAT", "data 0").unwrap(),
+            chunk_from_strings("fcTL", "frame 1").unwrap(),
+            chunk_from_strings("fdAT", "data 1").unwrap(),
+            chunk_from_strings("IEND", "").unwrap(),
+        ]);
+        let actl = png.animation_control().unwrap().unwrap();
+        assert_eq!(actl.num_frames, 2);
+
+        let n_frame_controls = png.chunks()
+            .iter()
+            .filter(|chunk| chunk.type_str() == Some(apng::FCTL))
+            .count();
+        assert_eq!(n_frame_controls, 2);
+    }
+
+    #[test]
+    fn test_trailing_data() {
+        let bytes: Vec<u8> = PNG_FILE.iter()
+            .chain(b"I am trailing data".iter())
+            .copied()
+            .collect();
+
+        let mut png = Png::try_from(bytes.as_ref()).unwrap();
+        assert_eq!(png.trailing_data(), b"I am trailing data");
+        assert_eq!(png.as_bytes(), bytes);
+
+        let removed = png.remove_trailing_data();
+        assert_eq!(removed, b"I am trailing data");
+        assert_eq!(png.as_bytes().len(), PNG_FILE.len());
+        assert_eq!(png.as_bytes(), PNG_FILE.to_vec());
+    }
+
+    #[test]
+    fn test_remove_chunks_after_iend() {
+        let mut png = Png::try_from(&PNG_FILE[..]).unwrap();
+        let chunk = chunk_from_strings("ruSt", "Message").unwrap();
+        png.append_chunk(chunk.clone());
+
+        let removed = png.remove_trailing_data();
+        assert_eq!(removed, chunk.as_bytes());
+        assert_eq!(png.as_bytes(), PNG_FILE.to_vec());
+    }
+
+    #[test]
+    fn test_byte_exact_roundtrip() {
+        let with_trailing_data: Vec<u8> = PNG_FILE.iter()
+            .chain(b"I am trailing data".iter())
+            .copied()
+            .collect();
+
+        let mut with_chunk_after_iend = Png::try_from(&PNG_FILE[..]).unwrap();
+        with_chunk_after_iend.append_chunk(chunk_from_strings("ruSt", "Message").unwrap());
+
+        let fixtures = [
+            PNG_FILE.to_vec(),
+            testing_png().as_bytes(),
+            with_trailing_data,
+            with_chunk_after_iend.as_bytes()
+        ];
+        for fixture in fixtures {
+            assert!(Png::is_byte_exact_roundtrip(&fixture));
+        }
+
+        let mut corrupted = PNG_FILE.to_vec();
+        corrupted[100] ^= 0xff;
+        assert!(!Png::is_byte_exact_roundtrip(&corrupted));
+    }
+
+    #[test]
+    fn test_roundtrip_mismatch() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+        assert_eq!(png.roundtrip_mismatch(&bytes), None);
+
+        // a wrong CRC is recomputed when writing
+        let (offset, chunk) = png.chunk_offsets()[1];
+        let crc_index = offset + chunk.as_bytes().len() - 2;
+        let mut corrupted = bytes.clone();
+        corrupted[crc_index] ^= 0xff;
+        let (tolerant, _) = Png::from_bytes_tolerant(&corrupted).unwrap();
+        assert_eq!(
+            tolerant.roundtrip_mismatch(&corrupted),
+            Some(RoundtripMismatch::CrcMismatch { offset: crc_index, index: 1 })
+        );
+
+        // the data of a chunk is changed
+        let mut data = chunk.data().to_vec();
+        data[0] ^= 0xff;
+        let mut modified = testing_png();
+        modified.iter_mut().nth(1).unwrap().set_data(data);
+        assert_eq!(
+            modified.roundtrip_mismatch(&bytes),
+            Some(RoundtripMismatch::ChunkBytes { offset: offset + 8, index: 1 })
+        );
+
+        // the trailing bytes are missing
+        let with_trailing_data: Vec<u8> = bytes.iter()
+            .chain(b"junk".iter())
+            .copied()
+            .collect();
+        assert_eq!(
+            png.roundtrip_mismatch(&with_trailing_data),
+            Some(RoundtripMismatch::TrailingData { offset: bytes.len() })
+        );
+    }
+
     #[test]
     fn test_png_trait_impls() {
         let chunk_bytes: Vec<u8> = testing_chunks()
@@ -334,6 +2409,113 @@ mod tests {
         let _png_string = format!("{}", png);
     }
 
+    #[test]
+    fn test_append_text() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.append_text("Comment", "Hidden in plain sight").unwrap();
+        assert!(png.append_text("Comment  with spaces", "text").is_err());
+        assert_eq!(png.len(), 4);
+
+        let reparsed = Png::try_from(png.as_bytes().as_slice()).unwrap();
+        assert_eq!(reparsed.chunks().last().unwrap().chunk_type().to_string(), IEND);
+        let text = text::TextChunk::try_from(reparsed.chunk_at(2).unwrap()).unwrap();
+        assert_eq!(text.keyword(), "Comment");
+        assert_eq!(text.text(), "Hidden in plain sight");
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.insert_chunk(1, chunk_from_strings("ruSt", "first").unwrap()).unwrap();
+        png.insert_chunk(2, chunk_from_strings("teSt", "second").unwrap()).unwrap();
+        let critical_bytes = png.critical_bytes();
+
+        for chunk in png.iter_mut().filter(|chunk| !chunk.chunk_type().is_critical()) {
+            let data = chunk.data().to_ascii_uppercase();
+            chunk.set_data(data);
+        }
+
+        let reparsed = Png::try_from(png.as_bytes().as_slice()).unwrap();
+        assert_eq!(reparsed.chunk_by_type("ruSt").unwrap().data(), b"FIRST");
+        assert_eq!(reparsed.chunk_by_type("teSt").unwrap().data(), b"SECOND");
+        assert_eq!(reparsed.critical_bytes(), critical_bytes);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+
+        // rebuild the PNG from the transformed chunks
+        let mut chunks: Vec<Chunk> = vec![];
+        for mut chunk in png {
+            if chunk.type_str() == Some("FrSt") {
+                chunk.set_data(b"Changed".to_vec());
+            }
+            chunks.push(chunk);
+        }
+        let rebuilt = Png::from_chunks(chunks);
+        assert_eq!(rebuilt.chunk_by_type("FrSt").unwrap().data(), b"Changed");
+
+        let rebuilt = Png::from_chunks(Png::try_from(bytes.as_slice()).unwrap().into_chunks());
+        assert_eq!(rebuilt.as_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_is_standard_chunk_type() {
+        assert!(is_standard_chunk_type("tEXt"));
+        assert!(is_standard_chunk_type("IDAT"));
+        assert!(!is_standard_chunk_type("teXt"));
+        assert!(!is_standard_chunk_type("ruSt"));
+    }
+
+    #[test]
+    fn test_suggest_standard_chunk_type() {
+        assert_eq!(edit_distance("idta", "idat"), 2);
+        assert_eq!(edit_distance("", "iend"), 4);
+        assert_eq!(edit_distance("ihdr", "ihdr"), 0);
+
+        assert_eq!(suggest_standard_chunk_type("IDTA"), Some(IDAT));
+        assert_eq!(suggest_standard_chunk_type("IHDr"), Some(IHDR));
+        assert_eq!(suggest_standard_chunk_type("teXt"), Some("tEXt"));
+        assert_eq!(suggest_standard_chunk_type("IDAT"), None);
+        assert_eq!(suggest_standard_chunk_type("teSt"), None);
+        assert_eq!(suggest_standard_chunk_type("ruSt"), None);
+
+        let mut png = Png::new_blank(1, 1).unwrap();
+        let result = png.redact_chunk("IDTA");
+        assert_eq!(result.unwrap_err().to_string(), "No chunk of type IDTA is found, did you mean IDAT?");
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let mut png = Png::with_capacity(100);
+        assert!(png.is_empty());
+        assert!(png.capacity() >= 100);
+
+        for chunk in testing_chunks() {
+            png.append_chunk(chunk);
+        }
+        png.reserve(200);
+        assert_eq!(png.len(), 3);
+        assert!(png.capacity() >= 203);
+    }
+
+    #[test]
+    fn test_display_summary() {
+        let png = Png::new_blank(1, 1).unwrap();
+        let summary = png.to_string();
+        assert!(summary.starts_with("PNG (standard signature, 3 chunks)"));
+        assert!(summary.contains("IHDR          13 bytes"));
+        assert!(summary.contains("IEND           0 bytes"));
+        assert_eq!(summary.lines().count(), 4);
+
+        // a chunk type that is not UTF-8 is escaped instead of panicking
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.insert_chunk(1, Chunk::new(ChunkType::from_bytes_lenient([b'R', 0xff, b'S', b't']), vec![0; 3])).unwrap();
+        assert!(png.to_string().contains("R\\xffSt           3 bytes"));
+    }
+
     // This is the raw bytes for a shrunken version of the `dice.png` image on Wikipedia
     const PNG_FILE: [u8; 4803] = [
         137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 50, 0, 0, 0, 50, 8,
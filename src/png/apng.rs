@@ -0,0 +1,119 @@
+use std::fmt::Display;
+
+use crate::Error;
+use crate::chunk::Chunk;
+
+/// Type code of the animation control chunk.
+pub const ACTL: &str = "acTL";
+
+/// Type code of the frame control chunk.
+pub const FCTL: &str = "fcTL";
+
+/// Type code of the frame data chunk.
+pub const FDAT: &str = "fdAT";
+
+/// Returns a short description of the chunk type if it is an APNG chunk type.
+pub fn label(chunk_type: &str) -> Option<&'static str> {
+    match chunk_type {
+        ACTL => Some("animation control"),
+        FCTL => Some("frame control"),
+        FDAT => Some("frame data"),
+        _ => None
+    }
+}
+
+/// Fields of the `acTL` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationControl {
+    /// Number of frames in the animation.
+    pub num_frames: u32,
+
+    /// Number of times to loop the animation, where 0 means infinite looping.
+    pub num_plays: u32
+}
+
+impl TryFrom<&Chunk> for AnimationControl {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        if chunk.type_str() != Some(ACTL) {
+            return Err(Box::new(ApngError::UnexpectedChunkType(chunk.chunk_type().to_string())));
+        }
+
+        let data = chunk.data();
+        if data.len() != 8 {
+            return Err(Box::new(ApngError::InvalidLength(data.len())));
+        }
+
+        Ok(AnimationControl {
+            num_frames: u32::from_be_bytes(data[0..4].try_into()?),
+            num_plays: u32::from_be_bytes(data[4..8].try_into()?)
+        })
+    }
+
+}
+
+#[derive(Debug)]
+pub enum ApngError {
+    /// The chunk is not an `acTL` chunk.
+    UnexpectedChunkType(String),
+
+    /// The `acTL` chunk data must have 8 bytes.
+    InvalidLength(usize)
+}
+
+impl std::error::Error for ApngError {}
+
+impl Display for ApngError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedChunkType(chunk_type) => {
+                write!(f, "Expected an {} chunk but got {}", ACTL, chunk_type)
+            },
+            Self::InvalidLength(n_bytes) => {
+                write!(f, "The {} chunk data must have 8 bytes while it has {}", ACTL, n_bytes)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    fn actl_chunk(num_frames: u32, num_plays: u32) -> Chunk {
+        let data: Vec<u8> = num_frames.to_be_bytes()
+            .iter()
+            .chain(num_plays.to_be_bytes().iter())
+            .copied()
+            .collect();
+        Chunk::new(ChunkType::from_str(ACTL).unwrap(), data)
+    }
+
+    #[test]
+    fn test_animation_control() {
+        let actl = AnimationControl::try_from(&actl_chunk(3, 0)).unwrap();
+        assert_eq!(actl.num_frames, 3);
+        assert_eq!(actl.num_plays, 0);
+    }
+
+    #[test]
+    fn test_invalid_animation_control() {
+        let chunk = Chunk::new(ChunkType::from_str(ACTL).unwrap(), vec![0, 0, 0, 1]);
+        assert!(AnimationControl::try_from(&chunk).is_err());
+
+        let chunk = Chunk::new(ChunkType::from_str(FCTL).unwrap(), vec![0; 8]);
+        assert!(AnimationControl::try_from(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_label() {
+        assert_eq!(label("fdAT"), Some("frame data"));
+        assert_eq!(label("IDAT"), None);
+    }
+}
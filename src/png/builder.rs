@@ -0,0 +1,128 @@
+use crate::{Result, chunk::Chunk};
+use super::{Png, PngError};
+
+/// Assembles the bytes of a PNG file from chunks in exactly the given order.
+///
+/// Unlike [`Png`], which guards its chunk layout,
+/// [`PngBuilder::build_unchecked`] writes whatever it is given,
+/// e.g., a file without a signature or with `IEND` before `IDAT`,
+/// which is useful for producing edge cases for PNG readers.
+#[derive(Debug, Default)]
+pub struct PngBuilder {
+    /// Whether the PNG signature is written before the chunks.
+    signature: bool,
+
+    chunks: Vec<Chunk>
+}
+
+impl PngBuilder {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts the file with the standard PNG signature.
+    pub fn signature(mut self) -> Self {
+        self.signature = true;
+        self
+    }
+
+    /// Appends a chunk after the chunks pushed so far.
+    pub fn push(mut self, chunk: Chunk) -> Self {
+        self.chunks.push(chunk);
+        self
+    }
+
+    /// Writes the signature, if any, and the chunks in order without any validation.
+    pub fn build_unchecked(self) -> Vec<u8> {
+
+        let mut bytes: Vec<u8> = vec![];
+
+        if self.signature {
+            bytes.extend(Png::STANDARD_HEADER);
+        }
+
+        for chunk in &self.chunks {
+            bytes.extend(chunk.as_bytes());
+        }
+
+        bytes
+    }
+
+    /// Writes the file like [`PngBuilder::build_unchecked`] after checking that
+    /// the signature is present and the chunks are accepted by [`Png::set_chunks`],
+    /// i.e., `IHDR` comes first, `IEND` comes last and singular chunks are unique,
+    /// and no chunk data is longer than [`Chunk::MAX_LENGTH`].
+    pub fn build(self) -> Result<Vec<u8>> {
+
+        if !self.signature {
+            return Err(Box::new(PngError::InvalidHeader));
+        }
+
+        let mut png = Png::from_chunks(vec![]);
+        png.set_chunks(self.chunks)?;
+
+        png.try_as_bytes()
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::chunk_type::ChunkType;
+    use super::super::{IHDR, IDAT, IEND};
+
+    fn testing_chunks() -> Vec<Chunk> {
+        Png::new_blank(1, 1).unwrap().into_chunks()
+    }
+
+    #[test]
+    fn test_build() {
+        let builder = testing_chunks()
+            .into_iter()
+            .fold(PngBuilder::new().signature(), PngBuilder::push);
+        let bytes = builder.build().unwrap();
+        assert_eq!(bytes, Png::new_blank(1, 1).unwrap().as_bytes());
+
+        // the signature is required
+        let builder = testing_chunks()
+            .into_iter()
+            .fold(PngBuilder::new(), PngBuilder::push);
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn test_build_unchecked_iend_before_idat() {
+        let chunks = testing_chunks();
+        let builder = PngBuilder::new()
+            .signature()
+            .push(chunks[0].clone())
+            .push(Chunk::new(ChunkType::from_str(IEND).unwrap(), vec![]))
+            .push(chunks[1].clone());
+
+        let bytes = builder.build_unchecked();
+        assert!(bytes.starts_with(&Png::STANDARD_HEADER));
+
+        // the chunks are written in the given order
+        let chunk_types: Vec<String> = Png::from_chunks_bytes(&bytes[Png::STANDARD_HEADER.len()..])
+            .unwrap()
+            .iter()
+            .map(|chunk| chunk.chunk_type().to_string())
+            .collect();
+        assert_eq!(chunk_types, vec![IHDR, IEND, IDAT]);
+
+        // the same layout is rejected when validated
+        let builder = PngBuilder::new()
+            .signature()
+            .push(chunks[0].clone())
+            .push(Chunk::new(ChunkType::from_str(IEND).unwrap(), vec![]))
+            .push(chunks[1].clone());
+        assert_eq!(
+            builder.build().unwrap_err().to_string(),
+            PngError::IendNotLast(IDAT.to_string()).to_string()
+        );
+    }
+}
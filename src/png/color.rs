@@ -0,0 +1,248 @@
+use std::fmt::Display;
+
+use crate::Error;
+use crate::chunk::Chunk;
+
+/// Type code of the image gamma chunk.
+pub const GAMA: &str = "gAMA";
+
+/// Type code of the standard RGB color space chunk.
+pub const SRGB: &str = "sRGB";
+
+/// Type code of the primary chromaticities chunk.
+pub const CHRM: &str = "cHRM";
+
+/// The values of `gAMA` and `cHRM` are stored multiplied by this factor.
+const SCALE: f64 = 100000.0;
+
+/// Fields of the `gAMA` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gamma {
+    /// Image gamma times 100000, e.g., 45455 for a gamma of 1/2.2.
+    pub value: u32
+}
+
+impl Gamma {
+
+    /// The image gamma as a floating point number.
+    pub fn gamma(&self) -> f64 {
+        self.value as f64 / SCALE
+    }
+
+}
+
+impl TryFrom<&Chunk> for Gamma {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        let data = checked_data(chunk, GAMA, 4)?;
+
+        Ok(Gamma {
+            value: u32::from_be_bytes(data.try_into()?)
+        })
+    }
+
+}
+
+/// Rendering intent of the `sRGB` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderingIntent {
+    Perceptual,
+    RelativeColorimetric,
+    Saturation,
+    AbsoluteColorimetric
+}
+
+impl TryFrom<u8> for RenderingIntent {
+
+    type Error = Error;
+
+    fn try_from(value: u8) -> std::result::Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Perceptual),
+            1 => Ok(Self::RelativeColorimetric),
+            2 => Ok(Self::Saturation),
+            3 => Ok(Self::AbsoluteColorimetric),
+            _ => Err(Box::new(ColorError::InvalidRenderingIntent(value)))
+        }
+    }
+
+}
+
+impl Display for RenderingIntent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Perceptual => write!(f, "perceptual"),
+            Self::RelativeColorimetric => write!(f, "relative colorimetric"),
+            Self::Saturation => write!(f, "saturation"),
+            Self::AbsoluteColorimetric => write!(f, "absolute colorimetric")
+        }
+    }
+}
+
+/// Fields of the `sRGB` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Srgb {
+    pub rendering_intent: RenderingIntent
+}
+
+impl TryFrom<&Chunk> for Srgb {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        let data = checked_data(chunk, SRGB, 1)?;
+
+        Ok(Srgb {
+            rendering_intent: RenderingIntent::try_from(data[0])?
+        })
+    }
+
+}
+
+/// Fields of the `cHRM` chunk, where each point is a pair of x and y
+/// CIE chromaticity coordinates times 100000.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Chromaticities {
+    pub white_point: (u32, u32),
+    pub red: (u32, u32),
+    pub green: (u32, u32),
+    pub blue: (u32, u32)
+}
+
+impl Chromaticities {
+
+    /// The white point as floating point coordinates.
+    pub fn white_point_xy(&self) -> (f64, f64) {
+        (self.white_point.0 as f64 / SCALE, self.white_point.1 as f64 / SCALE)
+    }
+
+}
+
+impl TryFrom<&Chunk> for Chromaticities {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        let data = checked_data(chunk, CHRM, 32)?;
+
+        // eight 4-byte values
+        let values: Vec<u32> = data.chunks_exact(4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect();
+
+        Ok(Chromaticities {
+            white_point: (values[0], values[1]),
+            red: (values[2], values[3]),
+            green: (values[4], values[5]),
+            blue: (values[6], values[7])
+        })
+    }
+
+}
+
+/// Checks the type and the data length of the chunk and returns its data.
+fn checked_data<'a>(chunk: &'a Chunk, chunk_type: &'static str, length: usize) -> std::result::Result<&'a [u8], Error> {
+
+    if chunk.type_str() != Some(chunk_type) {
+        return Err(Box::new(ColorError::UnexpectedChunkType {
+            expected: chunk_type,
+            found: chunk.chunk_type().to_string()
+        }));
+    }
+
+    let data = chunk.data();
+    if data.len() != length {
+        return Err(Box::new(ColorError::InvalidLength {
+            chunk_type,
+            expected: length,
+            found: data.len()
+        }));
+    }
+
+    Ok(data)
+}
+
+#[derive(Debug)]
+pub enum ColorError {
+    /// The chunk is not of the `expected` type.
+    UnexpectedChunkType { expected: &'static str, found: String },
+
+    /// The chunk data does not have the `expected` number of bytes.
+    InvalidLength { chunk_type: &'static str, expected: usize, found: usize },
+
+    /// The rendering intent of the `sRGB` chunk must be 0 to 3.
+    InvalidRenderingIntent(u8)
+}
+
+impl std::error::Error for ColorError {}
+
+impl Display for ColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedChunkType { expected, found } => {
+                write!(f, "Expected a {} chunk but got {}", expected, found)
+            },
+            Self::InvalidLength { chunk_type, expected, found } => {
+                write!(f, "The {} chunk data must have {} bytes while it has {}", chunk_type, expected, found)
+            },
+            Self::InvalidRenderingIntent(value) => {
+                write!(f, "The rendering intent of the {} chunk must be 0 to 3 while it is {}", SRGB, value)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_gamma() {
+        let chunk = Chunk::new(ChunkType::from_str(GAMA).unwrap(), 45455u32.to_be_bytes().to_vec());
+        let gamma = Gamma::try_from(&chunk).unwrap();
+        assert_eq!(gamma.value, 45455);
+        assert_eq!(gamma.gamma(), 0.45455);
+
+        let chunk = Chunk::new(ChunkType::from_str(GAMA).unwrap(), vec![0; 3]);
+        assert!(Gamma::try_from(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_srgb() {
+        let chunk = Chunk::new(ChunkType::from_str(SRGB).unwrap(), vec![1]);
+        let srgb = Srgb::try_from(&chunk).unwrap();
+        assert_eq!(srgb.rendering_intent, RenderingIntent::RelativeColorimetric);
+        assert_eq!(srgb.rendering_intent.to_string(), "relative colorimetric");
+
+        let chunk = Chunk::new(ChunkType::from_str(SRGB).unwrap(), vec![4]);
+        assert!(Srgb::try_from(&chunk).is_err());
+
+        let chunk = Chunk::new(ChunkType::from_str(GAMA).unwrap(), vec![0]);
+        assert!(Srgb::try_from(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_chromaticities() {
+        // the sRGB primaries and the D65 white point
+        let values: [u32; 8] = [31270, 32900, 64000, 33000, 30000, 60000, 15000, 6000];
+        let data: Vec<u8> = values.iter().flat_map(|value| value.to_be_bytes()).collect();
+        let chunk = Chunk::new(ChunkType::from_str(CHRM).unwrap(), data);
+        let chrm = Chromaticities::try_from(&chunk).unwrap();
+        assert_eq!(chrm.white_point, (31270, 32900));
+        assert_eq!(chrm.red, (64000, 33000));
+        assert_eq!(chrm.green, (30000, 60000));
+        assert_eq!(chrm.blue, (15000, 6000));
+        assert_eq!(chrm.white_point_xy(), (0.3127, 0.329));
+
+        let chunk = Chunk::new(ChunkType::from_str(CHRM).unwrap(), vec![0; 31]);
+        assert!(Chromaticities::try_from(&chunk).is_err());
+    }
+}
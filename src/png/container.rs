@@ -0,0 +1,169 @@
+use std::fmt::Display;
+
+use crate::Result;
+
+/// A named secret stored in a container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    pub data: Vec<u8>
+}
+
+impl Entry {
+
+    pub fn new(name: &str, data: Vec<u8>) -> Self {
+        Entry {
+            name: name.to_string(),
+            data
+        }
+    }
+
+}
+
+/// Encodes the entries into the container format.
+/// The container starts with a 4-byte entry count,
+/// followed by the records of the entries.
+/// Each record consists of a 4-byte name length, the name,
+/// a 4-byte data length and the data.
+/// All integers are stored in big-endian byte order.
+pub fn encode(entries: &[Entry]) -> Vec<u8> {
+
+    let mut bytes: Vec<u8> = vec![];
+
+    // number of entries
+    bytes.extend((entries.len() as u32).to_be_bytes());
+
+    // records of entries
+    for entry in entries {
+        bytes.extend((entry.name.len() as u32).to_be_bytes());
+        bytes.extend(entry.name.as_bytes());
+        bytes.extend((entry.data.len() as u32).to_be_bytes());
+        bytes.extend(&entry.data);
+    }
+
+    bytes
+}
+
+/// Decodes the entries from the bytes in the container format.
+pub fn decode(bytes: &[u8]) -> Result<Vec<Entry>> {
+
+    // convert to a mutable slice
+    let mut bytes = bytes;
+
+    // number of entries
+    let n_entries = take_u32(&mut bytes)?;
+
+    // collect entries
+    let mut entries: Vec<Entry> = vec![];
+    for _ in 0..n_entries {
+
+        // extract the name
+        let name_length = take_u32(&mut bytes)? as usize;
+        let name = take_bytes(&mut bytes, name_length)?;
+        let name = String::from_utf8(name.to_vec())
+            .map_err(|_| Box::new(ContainerError::InvalidName))?;
+
+        // extract the data
+        let data_length = take_u32(&mut bytes)? as usize;
+        let data = take_bytes(&mut bytes, data_length)?.to_vec();
+
+        entries.push(Entry { name, data });
+    }
+
+    // there should be nothing left
+    if !bytes.is_empty() {
+        return Err(Box::new(ContainerError::TrailingBytes(bytes.len())));
+    }
+
+    Ok(entries)
+}
+
+/// Finds the entry with the specified name.
+pub fn find<'a>(entries: &'a [Entry], name: &str) -> Option<&'a Entry> {
+    entries.iter().find(|entry| entry.name == name)
+}
+
+/// Takes the given number of bytes from the front of the slice.
+fn take_bytes<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+
+    if bytes.len() < n {
+        return Err(Box::new(ContainerError::UnexpectedEnd));
+    }
+
+    let (head, tail) = bytes.split_at(n);
+    *bytes = tail;
+
+    Ok(head)
+}
+
+/// Takes a big-endian 4-byte unsigned integer from the front of the slice.
+fn take_u32(bytes: &mut &[u8]) -> Result<u32> {
+    let head: [u8; 4] = take_bytes(bytes, 4)?.try_into()?;
+    Ok(u32::from_be_bytes(head))
+}
+
+#[derive(Debug)]
+pub enum ContainerError {
+    /// The input ends in the middle of a record.
+    UnexpectedEnd,
+
+    /// The entry name is not valid UTF-8.
+    InvalidName,
+
+    /// There are extra bytes after the last record.
+    TrailingBytes(usize)
+}
+
+impl std::error::Error for ContainerError {}
+
+impl Display for ContainerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEnd => {
+                write!(f, "The container data ends unexpectedly")
+            },
+            Self::InvalidName => {
+                write!(f, "The entry name in the container is not valid UTF-8")
+            },
+            Self::TrailingBytes(n_bytes) => {
+                write!(f, "There are {} unexpected bytes after the last entry of the container", n_bytes)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn testing_entries() -> Vec<Entry> {
+        vec![
+            Entry::new("first", "I am the first secret".as_bytes().to_vec()),
+            Entry::new("second", vec![0, 1, 2, 3])
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let entries = testing_entries();
+        let bytes = encode(&entries);
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(decoded, entries);
+    }
+
+    #[test]
+    fn test_find_entry() {
+        let entries = decode(&encode(&testing_entries())).unwrap();
+        let entry = find(&entries, "second").unwrap();
+        assert_eq!(entry.data, vec![0, 1, 2, 3]);
+        assert!(find(&entries, "third").is_none());
+    }
+
+    #[test]
+    fn test_truncated_container() {
+        let bytes = encode(&testing_entries());
+        let decoded = decode(&bytes[..bytes.len() - 1]);
+        assert!(decoded.is_err());
+    }
+}
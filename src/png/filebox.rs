@@ -0,0 +1,142 @@
+use std::{
+    fmt::Display,
+    path::Path
+};
+
+use crate::Result;
+
+/// A file embedded together with its original file name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileBox {
+    pub filename: String,
+    pub data: Vec<u8>
+}
+
+impl FileBox {
+
+    /// Creates a file box, where only the last component of the path is kept as the file name.
+    pub fn new<P: AsRef<Path>>(path: P, data: Vec<u8>) -> Result<Self> {
+
+        let filename = path.as_ref()
+            .file_name()
+            .and_then(|filename| filename.to_str())
+            .ok_or_else(|| Box::new(FileBoxError::InvalidFilename(path.as_ref().display().to_string())))?;
+
+        Ok(FileBox {
+            filename: filename.to_string(),
+            data
+        })
+    }
+
+}
+
+/// Encodes the file box as a 2-byte file name length, the file name and then the file bytes.
+/// The length is stored in big-endian byte order.
+pub fn encode(file_box: &FileBox) -> Result<Vec<u8>> {
+
+    let filename_length: u16 = file_box.filename.len()
+        .try_into()
+        .map_err(|_| Box::new(FileBoxError::InvalidFilename(file_box.filename.clone())))?;
+
+    Ok(
+        filename_length.to_be_bytes()
+            .iter()
+            .chain(file_box.filename.as_bytes().iter())
+            .chain(file_box.data.iter())
+            .copied()
+            .collect()
+    )
+}
+
+/// Decodes the file box from the bytes.
+/// The file name must be a plain file name without any directory components.
+pub fn decode(bytes: &[u8]) -> Result<FileBox> {
+
+    // extract the file name length
+    if bytes.len() < 2 {
+        return Err(Box::new(FileBoxError::UnexpectedEnd));
+    }
+    let filename_length = u16::from_be_bytes([bytes[0], bytes[1]]) as usize;
+    let bytes = &bytes[2..];
+
+    // extract the file name
+    if bytes.len() < filename_length {
+        return Err(Box::new(FileBoxError::UnexpectedEnd));
+    }
+    let filename = String::from_utf8(bytes[..filename_length].to_vec())
+        .map_err(|_| Box::new(FileBoxError::InvalidFilename(String::from_utf8_lossy(&bytes[..filename_length]).into_owned())))?;
+
+    // reject file names which could escape the output directory
+    let is_plain_filename = Path::new(&filename)
+        .file_name()
+        .is_some_and(|name| name == filename.as_str());
+    if !is_plain_filename {
+        return Err(Box::new(FileBoxError::InvalidFilename(filename)));
+    }
+
+    Ok(FileBox {
+        filename,
+        data: bytes[filename_length..].to_vec()
+    })
+}
+
+#[derive(Debug)]
+pub enum FileBoxError {
+    /// The input ends before the file name.
+    UnexpectedEnd,
+
+    /// The file name is empty, too long, not valid UTF-8 or contains directory components.
+    InvalidFilename(String)
+}
+
+impl std::error::Error for FileBoxError {}
+
+impl Display for FileBoxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEnd => {
+                write!(f, "The embedded file data ends unexpectedly")
+            },
+            Self::InvalidFilename(filename) => {
+                write!(f, "The file name {:?} is invalid", filename)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::{Read, Write}};
+
+    #[test]
+    fn test_round_trip() {
+        let filepath = std::env::temp_dir().join("hackpng_test_filebox.txt");
+        File::create(&filepath).unwrap().write_all(b"I am an embedded file").unwrap();
+
+        let mut data = Vec::new();
+        File::open(&filepath).unwrap().read_to_end(&mut data).unwrap();
+        let file_box = FileBox::new(&filepath, data).unwrap();
+        let decoded = decode(&encode(&file_box).unwrap()).unwrap();
+
+        assert_eq!(decoded.filename, "hackpng_test_filebox.txt");
+        assert_eq!(decoded.data, b"I am an embedded file");
+
+        std::fs::remove_file(filepath).unwrap();
+    }
+
+    #[test]
+    fn test_unsafe_filename() {
+        let file_box = FileBox {
+            filename: "../secret.txt".to_string(),
+            data: vec![]
+        };
+        assert!(decode(&encode(&file_box).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_truncated_file_box() {
+        assert!(decode(&[0, 10, b'a']).is_err());
+    }
+}
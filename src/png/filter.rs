@@ -0,0 +1,131 @@
+use crate::chunk::Chunk;
+
+/// Filter of chunks by the property bits of their types and their data lengths.
+/// A chunk matches if it has all the required properties.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChunkFilter {
+    /// Only matches ancillary chunks.
+    pub ancillary: bool,
+
+    /// Only matches private chunks.
+    pub private: bool,
+
+    /// Only matches chunks which are safe to copy.
+    pub safe_to_copy: bool,
+
+    /// Only matches chunks with at least this many data bytes.
+    pub min_length: Option<u32>,
+
+    /// Only matches chunks with at most this many data bytes.
+    pub max_length: Option<u32>
+}
+
+impl ChunkFilter {
+
+    /// Checks if the chunk passes the filter.
+    pub fn matches(&self, chunk: &Chunk) -> bool {
+        let chunk_type = chunk.chunk_type();
+        (!self.ancillary || !chunk_type.is_critical())
+            && (!self.private || !chunk_type.is_public())
+            && (!self.safe_to_copy || chunk_type.is_safe_to_copy())
+            && self.min_length.is_none_or(|min_length| chunk.length() >= min_length)
+            && self.max_length.is_none_or(|max_length| chunk.length() <= max_length)
+    }
+
+    /// Returns the chunks passing the filter together with their indices.
+    pub fn apply<'a>(&self, chunks: &'a [Chunk]) -> Vec<(usize, &'a Chunk)> {
+        chunks.iter()
+            .enumerate()
+            .filter(|(_, chunk)| self.matches(chunk))
+            .collect()
+    }
+
+    /// Returns the chunks passing both the filter and the `extra` predicate together with their indices,
+    /// e.g., to further match the chunk types against a pattern.
+    pub fn apply_with<'a>(&self, chunks: &'a [Chunk], extra: impl Fn(&Chunk) -> bool) -> Vec<(usize, &'a Chunk)> {
+        chunks.iter()
+            .enumerate()
+            .filter(|(_, chunk)| self.matches(chunk) && extra(chunk))
+            .collect()
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    fn testing_chunks() -> Vec<Chunk> {
+        ["IHDR", "tEXt", "ruSt", "ruST", "IDAT", "IEND"]
+            .iter()
+            .map(|chunk_type| Chunk::new(ChunkType::from_str(chunk_type).unwrap(), vec![]))
+            .collect()
+    }
+
+    fn filtered_types(filter: ChunkFilter) -> Vec<String> {
+        filter.apply(&testing_chunks())
+            .iter()
+            .map(|(_, chunk)| chunk.chunk_type().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_no_filter() {
+        assert_eq!(filtered_types(ChunkFilter::default()).len(), 6);
+    }
+
+    #[test]
+    fn test_ancillary_private_filter() {
+        let filter = ChunkFilter {
+            ancillary: true,
+            private: true,
+            ..Default::default()
+        };
+        assert_eq!(filtered_types(filter), vec!["ruSt", "ruST"]);
+    }
+
+    #[test]
+    fn test_safe_to_copy_filter() {
+        let filter = ChunkFilter {
+            ancillary: true,
+            private: true,
+            safe_to_copy: true,
+            ..Default::default()
+        };
+        assert_eq!(filtered_types(filter), vec!["ruSt"]);
+        assert_eq!(filter.apply(&testing_chunks())[0].0, 2);
+    }
+
+    #[test]
+    fn test_length_filter() {
+        let chunks: Vec<Chunk> = [0, 10, 100, 1000]
+            .iter()
+            .map(|length| Chunk::new(ChunkType::from_str("ruSt").unwrap(), vec![0; *length]))
+            .collect();
+        let filtered_indices = |filter: ChunkFilter| -> Vec<usize> {
+            filter.apply(&chunks).iter().map(|(index, _)| *index).collect()
+        };
+
+        let filter = ChunkFilter { min_length: Some(10), ..Default::default() };
+        assert_eq!(filtered_indices(filter), vec![1, 2, 3]);
+
+        let filter = ChunkFilter { max_length: Some(100), ..Default::default() };
+        assert_eq!(filtered_indices(filter), vec![0, 1, 2]);
+
+        let filter = ChunkFilter { min_length: Some(10), max_length: Some(100), ..Default::default() };
+        assert_eq!(filtered_indices(filter), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_apply_with() {
+        let filter = ChunkFilter { ancillary: true, ..Default::default() };
+        let filtered: Vec<usize> = filter.apply_with(&testing_chunks(), |chunk| chunk.type_str() != Some("tEXt"))
+            .iter()
+            .map(|(index, _)| *index)
+            .collect();
+        assert_eq!(filtered, vec![2, 3]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    str::FromStr,
+    fmt::Display
+};
+
+use crate::{Result, Error};
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+
+use super::IHDR;
+
+/// Fields of the `IHDR` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ihdr {
+    pub width: u32,
+    pub height: u32,
+    pub bit_depth: u8,
+    pub color_type: u8,
+    pub compression_method: u8,
+    pub filter_method: u8,
+    pub interlace_method: u8
+}
+
+impl Ihdr {
+
+    /// The `IHDR` chunk data always has 13 bytes.
+    pub const LENGTH: usize = 13;
+
+    /// Maximum width and height of an image, which is 2^31 - 1 by the PNG specification.
+    pub const MAX_DIMENSION: u32 = (1 << 31) - 1;
+
+    /// Interlace method of Adam7 interlacing, while 0 means no interlacing.
+    pub const ADAM7: u8 = 1;
+
+    /// Header of an 8-bit grayscale, non-interlaced image of the given size.
+    pub fn grayscale(width: u32, height: u32) -> Result<Self> {
+
+        if width == 0 || height == 0 || width > Self::MAX_DIMENSION || height > Self::MAX_DIMENSION {
+            return Err(Box::new(IhdrError::InvalidDimensions(width, height)));
+        }
+
+        Ok(Ihdr {
+            width,
+            height,
+            bit_depth: 8,
+            color_type: 0,
+            compression_method: 0,
+            filter_method: 0,
+            interlace_method: 0
+        })
+    }
+
+    /// Checks if the image is interlaced with Adam7.
+    pub fn is_interlaced(&self) -> bool {
+        self.interlace_method == Self::ADAM7
+    }
+
+    /// Builds the `IHDR` chunk.
+    pub fn to_chunk(&self) -> Chunk {
+
+        let data: Vec<u8> = self.width.to_be_bytes()
+            .iter()
+            .chain(self.height.to_be_bytes().iter())
+            .chain([
+                self.bit_depth,
+                self.color_type,
+                self.compression_method,
+                self.filter_method,
+                self.interlace_method
+            ].iter())
+            .copied()
+            .collect();
+
+        Chunk::new(ChunkType::from_str(IHDR).unwrap(), data)
+    }
+
+}
+
+impl TryFrom<&Chunk> for Ihdr {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        if chunk.type_str() != Some(IHDR) {
+            return Err(Box::new(IhdrError::UnexpectedChunkType(chunk.chunk_type().to_string())));
+        }
+
+        let data = chunk.data();
+        if data.len() != Self::LENGTH {
+            return Err(Box::new(IhdrError::InvalidLength(data.len())));
+        }
+
+        Ok(Ihdr {
+            width: u32::from_be_bytes(data[0..4].try_into()?),
+            height: u32::from_be_bytes(data[4..8].try_into()?),
+            bit_depth: data[8],
+            color_type: data[9],
+            compression_method: data[10],
+            filter_method: data[11],
+            interlace_method: data[12]
+        })
+    }
+
+}
+
+#[derive(Debug)]
+pub enum IhdrError {
+    /// The chunk is not an `IHDR` chunk.
+    UnexpectedChunkType(String),
+
+    /// The `IHDR` chunk data must have 13 bytes.
+    InvalidLength(usize),
+
+    /// Both the width and height must be positive and at most [`Ihdr::MAX_DIMENSION`].
+    InvalidDimensions(u32, u32)
+}
+
+impl std::error::Error for IhdrError {}
+
+impl Display for IhdrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedChunkType(chunk_type) => {
+                write!(f, "Expected an {} chunk but got {}", IHDR, chunk_type)
+            },
+            Self::InvalidLength(n_bytes) => {
+                write!(f, "The {} chunk data must have {} bytes while it has {}", IHDR, Ihdr::LENGTH, n_bytes)
+            },
+            Self::InvalidDimensions(width, height) => {
+                write!(f, "The image dimensions {}x{} are invalid", width, height)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ihdr_round_trip() {
+        let ihdr = Ihdr::grayscale(50, 30).unwrap();
+        let chunk = ihdr.to_chunk();
+        assert_eq!(chunk.length(), 13);
+        assert_eq!(Ihdr::try_from(&chunk).unwrap(), ihdr);
+    }
+
+    #[test]
+    fn test_invalid_ihdr() {
+        assert!(Ihdr::grayscale(0, 1).is_err());
+
+        let chunk = Chunk::new(ChunkType::from_str(IHDR).unwrap(), vec![0; 12]);
+        assert!(Ihdr::try_from(&chunk).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt::Display;
+
+use crate::Error;
+use crate::chunk::Chunk;
+
+/// Type code of the significant bits chunk.
+pub const SBIT: &str = "sBIT";
+
+/// Type code of the background color chunk.
+pub const BKGD: &str = "bKGD";
+
+/// Type code of the physical pixel dimensions chunk.
+pub const PHYS: &str = "pHYs";
+
+/// Number of meters in an inch.
+const METERS_PER_INCH: f64 = 0.0254;
+
+/// Fields of the `sBIT` chunk, i.e., the number of significant bits
+/// of each channel in the original image.
+/// There are 1 to 4 values depending on the color type,
+/// e.g., red, green and blue for a truecolor image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignificantBits {
+    pub bits: Vec<u8>
+}
+
+impl TryFrom<&Chunk> for SignificantBits {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        let data = checked_data(chunk, SBIT)?;
+        if data.is_empty() || data.len() > 4 {
+            return Err(Box::new(MiscError::InvalidLength { chunk_type: SBIT, found: data.len() }));
+        }
+
+        Ok(SignificantBits {
+            bits: data.to_vec()
+        })
+    }
+
+}
+
+/// Fields of the `bKGD` chunk, whose layout depends on the color type of the image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    /// Index into the palette of an indexed-color image.
+    PaletteIndex(u8),
+
+    /// Gray level of a grayscale image.
+    Gray(u16),
+
+    /// Red, green and blue levels of a truecolor image.
+    Rgb(u16, u16, u16)
+}
+
+impl TryFrom<&Chunk> for Background {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        let data = checked_data(chunk, BKGD)?;
+        let sample = |index: usize| u16::from_be_bytes([data[2 * index], data[2 * index + 1]]);
+
+        // the layout is told apart by the data length
+        match data.len() {
+            1 => Ok(Background::PaletteIndex(data[0])),
+            2 => Ok(Background::Gray(sample(0))),
+            6 => Ok(Background::Rgb(sample(0), sample(1), sample(2))),
+            length => Err(Box::new(MiscError::InvalidLength { chunk_type: BKGD, found: length }))
+        }
+    }
+
+}
+
+impl Display for Background {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PaletteIndex(index) => write!(f, "palette index {}", index),
+            Self::Gray(level) => write!(f, "gray {}", level),
+            Self::Rgb(red, green, blue) => write!(f, "RGB ({}, {}, {})", red, green, blue)
+        }
+    }
+}
+
+/// Unit of the `pHYs` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhysicalUnit {
+    /// Only the aspect ratio of the pixels is known.
+    Unknown,
+
+    Meter
+}
+
+/// Fields of the `pHYs` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhysicalDimensions {
+    pub pixels_per_unit_x: u32,
+    pub pixels_per_unit_y: u32,
+    pub unit: PhysicalUnit
+}
+
+impl PhysicalDimensions {
+
+    /// The horizontal and vertical resolutions in dots per inch,
+    /// or `None` if the unit is unknown.
+    pub fn dpi(&self) -> Option<(f64, f64)> {
+        match self.unit {
+            PhysicalUnit::Meter => Some((
+                self.pixels_per_unit_x as f64 * METERS_PER_INCH,
+                self.pixels_per_unit_y as f64 * METERS_PER_INCH
+            )),
+            PhysicalUnit::Unknown => None
+        }
+    }
+
+}
+
+impl TryFrom<&Chunk> for PhysicalDimensions {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        let data = checked_data(chunk, PHYS)?;
+        if data.len() != 9 {
+            return Err(Box::new(MiscError::InvalidLength { chunk_type: PHYS, found: data.len() }));
+        }
+
+        let unit = match data[8] {
+            0 => PhysicalUnit::Unknown,
+            1 => PhysicalUnit::Meter,
+            value => return Err(Box::new(MiscError::InvalidUnit(value)))
+        };
+
+        Ok(PhysicalDimensions {
+            pixels_per_unit_x: u32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+            pixels_per_unit_y: u32::from_be_bytes([data[4], data[5], data[6], data[7]]),
+            unit
+        })
+    }
+
+}
+
+/// Checks the type of the chunk and returns its data.
+fn checked_data<'a>(chunk: &'a Chunk, chunk_type: &'static str) -> std::result::Result<&'a [u8], Error> {
+
+    if chunk.type_str() != Some(chunk_type) {
+        return Err(Box::new(MiscError::UnexpectedChunkType {
+            expected: chunk_type,
+            found: chunk.chunk_type().to_string()
+        }));
+    }
+
+    Ok(chunk.data())
+}
+
+#[derive(Debug)]
+pub enum MiscError {
+    /// The chunk is not of the `expected` type.
+    UnexpectedChunkType { expected: &'static str, found: String },
+
+    /// The chunk data has a number of bytes not allowed for its type.
+    InvalidLength { chunk_type: &'static str, found: usize },
+
+    /// The unit of the `pHYs` chunk must be 0 or 1.
+    InvalidUnit(u8)
+}
+
+impl std::error::Error for MiscError {}
+
+impl Display for MiscError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedChunkType { expected, found } => {
+                write!(f, "Expected a {} chunk but got {}", expected, found)
+            },
+            Self::InvalidLength { chunk_type, found } => {
+                write!(f, "The {} chunk data cannot have {} bytes", chunk_type, found)
+            },
+            Self::InvalidUnit(value) => {
+                write!(f, "The unit of the {} chunk must be 0 or 1 while it is {}", PHYS, value)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_significant_bits() {
+        let chunk = Chunk::new(ChunkType::from_str(SBIT).unwrap(), vec![5, 6, 5]);
+        let sbit = SignificantBits::try_from(&chunk).unwrap();
+        assert_eq!(sbit.bits, vec![5, 6, 5]);
+
+        let chunk = Chunk::new(ChunkType::from_str(SBIT).unwrap(), vec![8; 5]);
+        assert!(SignificantBits::try_from(&chunk).is_err());
+
+        let chunk = Chunk::new(ChunkType::from_str(BKGD).unwrap(), vec![8]);
+        assert!(SignificantBits::try_from(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_background() {
+        let chunk = Chunk::new(ChunkType::from_str(BKGD).unwrap(), vec![3]);
+        assert_eq!(Background::try_from(&chunk).unwrap(), Background::PaletteIndex(3));
+
+        let chunk = Chunk::new(ChunkType::from_str(BKGD).unwrap(), vec![0x01, 0x02]);
+        assert_eq!(Background::try_from(&chunk).unwrap(), Background::Gray(0x0102));
+
+        let chunk = Chunk::new(ChunkType::from_str(BKGD).unwrap(), vec![0, 255, 0, 128, 0, 0]);
+        let background = Background::try_from(&chunk).unwrap();
+        assert_eq!(background, Background::Rgb(255, 128, 0));
+        assert_eq!(background.to_string(), "RGB (255, 128, 0)");
+
+        let chunk = Chunk::new(ChunkType::from_str(BKGD).unwrap(), vec![0; 4]);
+        assert!(Background::try_from(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_physical_dimensions() {
+        // 2835 pixels per meter is about 72 DPI
+        let data = [2835u32.to_be_bytes(), 5669u32.to_be_bytes()].concat()
+            .into_iter()
+            .chain([1])
+            .collect();
+        let chunk = Chunk::new(ChunkType::from_str(PHYS).unwrap(), data);
+        let phys = PhysicalDimensions::try_from(&chunk).unwrap();
+        assert_eq!(phys.pixels_per_unit_x, 2835);
+        assert_eq!(phys.pixels_per_unit_y, 5669);
+        assert_eq!(phys.unit, PhysicalUnit::Meter);
+        let (dpi_x, dpi_y) = phys.dpi().unwrap();
+        assert_eq!((dpi_x.round(), dpi_y.round()), (72.0, 144.0));
+
+        // only the aspect ratio is known
+        let chunk = Chunk::new(ChunkType::from_str(PHYS).unwrap(), vec![0, 0, 0, 1, 0, 0, 0, 2, 0]);
+        assert!(PhysicalDimensions::try_from(&chunk).unwrap().dpi().is_none());
+
+        let chunk = Chunk::new(ChunkType::from_str(PHYS).unwrap(), vec![0, 0, 0, 1, 0, 0, 0, 2, 2]);
+        assert!(PhysicalDimensions::try_from(&chunk).is_err());
+
+        let chunk = Chunk::new(ChunkType::from_str(PHYS).unwrap(), vec![0; 8]);
+        assert!(PhysicalDimensions::try_from(&chunk).is_err());
+    }
+}
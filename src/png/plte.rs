@@ -0,0 +1,106 @@
+use std::fmt::Display;
+
+use crate::Error;
+use crate::chunk::Chunk;
+
+use super::PLTE;
+
+/// Entries of the `PLTE` chunk, each of which is a red, green and blue triple.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    pub entries: Vec<(u8, u8, u8)>
+}
+
+impl Palette {
+
+    /// The maximum number of palette entries.
+    pub const MAX_ENTRIES: usize = 256;
+
+    /// The number of palette entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if the palette has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+}
+
+impl TryFrom<&Chunk> for Palette {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        if chunk.type_str() != Some(PLTE) {
+            return Err(Box::new(PaletteError::UnexpectedChunkType(chunk.chunk_type().to_string())));
+        }
+
+        // each entry takes 3 bytes
+        let data = chunk.data();
+        if data.is_empty() || !data.len().is_multiple_of(3) || data.len() / 3 > Self::MAX_ENTRIES {
+            return Err(Box::new(PaletteError::InvalidLength(data.len())));
+        }
+
+        Ok(Palette {
+            entries: data.chunks_exact(3)
+                .map(|entry| (entry[0], entry[1], entry[2]))
+                .collect()
+        })
+    }
+
+}
+
+#[derive(Debug)]
+pub enum PaletteError {
+    /// The chunk is not a `PLTE` chunk.
+    UnexpectedChunkType(String),
+
+    /// The `PLTE` chunk data must have a positive multiple of 3 bytes and at most 256 entries.
+    InvalidLength(usize)
+}
+
+impl std::error::Error for PaletteError {}
+
+impl Display for PaletteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedChunkType(chunk_type) => {
+                write!(f, "Expected a {} chunk but got {}", PLTE, chunk_type)
+            },
+            Self::InvalidLength(n_bytes) => {
+                write!(f, "The {} chunk data must have a positive multiple of 3 bytes (at most {} entries) while it has {}", PLTE, Palette::MAX_ENTRIES, n_bytes)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_palette() {
+        let chunk = Chunk::new(ChunkType::from_str(PLTE).unwrap(), vec![255, 0, 0, 0, 255, 0, 0, 0, 255]);
+        let palette = Palette::try_from(&chunk).unwrap();
+        assert_eq!(palette.len(), 3);
+        assert_eq!(palette.entries, vec![(255, 0, 0), (0, 255, 0), (0, 0, 255)]);
+    }
+
+    #[test]
+    fn test_invalid_palette() {
+        let chunk = Chunk::new(ChunkType::from_str(PLTE).unwrap(), vec![255, 0, 0, 0]);
+        assert!(Palette::try_from(&chunk).is_err());
+
+        let chunk = Chunk::new(ChunkType::from_str(PLTE).unwrap(), vec![]);
+        assert!(Palette::try_from(&chunk).is_err());
+
+        let chunk = Chunk::new(ChunkType::from_str("tRNS").unwrap(), vec![0; 3]);
+        assert!(Palette::try_from(&chunk).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{Result, chunk::Chunk, chunk_type::ChunkType};
+use super::{Png, PngError, IEND};
+
+/// A fix applied by [`repair`] to a broken PNG.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Repair {
+    /// The signature is missing and has been prepended.
+    MissingSignature,
+
+    /// The signature is corrupt and has been replaced.
+    CorruptSignature,
+
+    /// The CRC of the chunk at the index is recomputed.
+    Crc { index: usize, chunk_type: String },
+
+    /// The bytes from the offset on do not form a chunk and have been dropped.
+    TruncatedChunk { offset: usize, length: usize },
+
+    /// The chunk at the offset has an invalid type code and has been dropped.
+    InvalidChunkType { offset: usize, chunk_type: String },
+
+    /// The `IEND` chunk is missing and has been appended.
+    MissingIend,
+
+    /// The data of the `IEND` chunk has been removed.
+    NonEmptyIend(usize),
+
+    /// The bytes after the `IEND` chunk have been dropped.
+    TrailingData(usize)
+}
+
+impl Display for Repair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingSignature => {
+                write!(f, "Added the missing PNG signature")
+            },
+            Self::CorruptSignature => {
+                write!(f, "Replaced the corrupt PNG signature")
+            },
+            Self::Crc { index, chunk_type } => {
+                write!(f, "Recomputed the CRC of chunk {} ({})", index, chunk_type)
+            },
+            Self::TruncatedChunk { offset, length } => {
+                write!(f, "Dropped {} bytes at byte offset {} that do not form a chunk", length, offset)
+            },
+            Self::InvalidChunkType { offset, chunk_type } => {
+                write!(f, "Dropped the chunk {} at byte offset {} with an invalid type code", chunk_type, offset)
+            },
+            Self::MissingIend => {
+                write!(f, "Appended the missing IEND chunk")
+            },
+            Self::NonEmptyIend(length) => {
+                write!(f, "Removed {} bytes of data from the IEND chunk", length)
+            },
+            Self::TrailingData(length) => {
+                write!(f, "Dropped {} bytes after IEND", length)
+            }
+        }
+    }
+}
+
+/// Restores a slightly broken PNG from the input bytes,
+/// i.e., fixes the signature, recomputes all CRCs, makes sure that an empty `IEND` chunk
+/// is the last chunk, and drops anything after it.
+/// Returns the repaired PNG together with the repairs performed.
+pub fn repair(bytes: &[u8]) -> Result<(Png, Vec<Repair>)> {
+
+    let mut repairs: Vec<Repair> = vec![];
+
+    // locate the first chunk
+    let mut offset = if bytes.starts_with(&Png::STANDARD_HEADER) {
+        Png::STANDARD_HEADER.len()
+    } else if starts_with_chunk(bytes) {
+        repairs.push(Repair::MissingSignature);
+        0
+    } else if bytes.len() > Png::STANDARD_HEADER.len() && starts_with_chunk(&bytes[Png::STANDARD_HEADER.len()..]) {
+        repairs.push(Repair::CorruptSignature);
+        Png::STANDARD_HEADER.len()
+    } else {
+        return Err(Box::new(PngError::InvalidHeader));
+    };
+
+    // collect the chunks up to IEND, where the CRCs are recomputed from the data,
+    // and the chunks are read leniently so that an invalid type code does not end the scan
+    let mut chunks: Vec<Chunk> = vec![];
+    while offset < bytes.len() {
+
+        let (chunk, crc) = match Chunk::from_bytes_unchecked_crc(&bytes[offset..], false) {
+            Ok(result) => result,
+            Err(_) => {
+                repairs.push(Repair::TruncatedChunk { offset, length: bytes.len() - offset });
+                offset = bytes.len();
+                break;
+            }
+        };
+
+        let chunk_offset = offset;
+        offset += Chunk::OVERHEAD + chunk.data_len();
+
+        // a chunk whose type code is not made of letters cannot be kept
+        if !chunk.chunk_type().bytes().iter().all(|byte| byte.is_ascii_alphabetic()) {
+            repairs.push(Repair::InvalidChunkType { offset: chunk_offset, chunk_type: chunk.chunk_type().to_string() });
+            continue;
+        }
+
+        if chunk.crc() != crc {
+            repairs.push(Repair::Crc { index: chunks.len(), chunk_type: chunk.chunk_type().to_string() });
+        }
+
+        let is_iend = chunk.type_str() == Some(IEND);
+        chunks.push(chunk);
+        if is_iend {
+            break;
+        }
+    }
+
+    // drop anything after IEND
+    if offset < bytes.len() {
+        repairs.push(Repair::TrailingData(bytes.len() - offset));
+    }
+
+    // make sure that the last chunk is an empty IEND
+    match chunks.last() {
+        Some(chunk) if chunk.type_str() == Some(IEND) => {
+            if chunk.data_len() > 0 {
+                repairs.push(Repair::NonEmptyIend(chunk.data_len()));
+                chunks.pop();
+                chunks.push(Chunk::new(ChunkType::from_str(IEND)?, vec![]));
+            }
+        },
+        _ => {
+            repairs.push(Repair::MissingIend);
+            chunks.push(Chunk::new(ChunkType::from_str(IEND)?, vec![]));
+        }
+    }
+
+    Ok((Png::from_chunks(chunks), repairs))
+}
+
+/// Checks if the bytes start with a complete chunk, regardless of its type code and CRC.
+fn starts_with_chunk(bytes: &[u8]) -> bool {
+    Chunk::from_bytes_unchecked_crc(bytes, false).is_ok()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn testing_png() -> Png {
+        let mut png = Png::new_blank(1, 1).unwrap();
+        png.insert_chunk(1, Chunk::from_text(ChunkType::from_str("ruSt").unwrap(), "Message")).unwrap();
+        png
+    }
+
+    #[test]
+    fn test_nothing_to_repair() {
+        let png = testing_png();
+        let (repaired, repairs) = repair(&png.as_bytes()).unwrap();
+        assert!(repairs.is_empty());
+        assert_eq!(repaired.as_bytes(), png.as_bytes());
+    }
+
+    #[test]
+    fn test_missing_iend() {
+        let mut png = testing_png();
+        png.remove_chunk(IEND).unwrap();
+        let mut bytes = png.as_bytes();
+        bytes.extend(b"junk");
+
+        let (repaired, repairs) = repair(&bytes).unwrap();
+        assert_eq!(repairs, vec![
+            Repair::TruncatedChunk { offset: bytes.len() - 4, length: 4 },
+            Repair::MissingIend
+        ]);
+
+        // the repaired PNG parses strictly
+        let reparsed = Png::try_from(repaired.as_bytes().as_slice()).unwrap();
+        assert!(reparsed.is_structurally_valid());
+        assert_eq!(reparsed.as_bytes(), testing_png().as_bytes());
+    }
+
+    #[test]
+    fn test_repair_crc_and_trailing_data() {
+        let png = testing_png();
+        let mut bytes: Vec<u8> = png.header().to_vec();
+        for (index, chunk) in png.chunks().iter().enumerate() {
+            if index == 1 {
+                bytes.extend(chunk.as_bytes_with_crc(0));
+            } else {
+                bytes.extend(chunk.as_bytes());
+            }
+        }
+        bytes.extend(b"trailing");
+
+        let (repaired, repairs) = repair(&bytes).unwrap();
+        assert_eq!(repairs, vec![
+            Repair::Crc { index: 1, chunk_type: "ruSt".to_string() },
+            Repair::TrailingData(8)
+        ]);
+        assert_eq!(repaired.as_bytes(), png.as_bytes());
+    }
+
+    #[test]
+    fn test_repair_signature() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+
+        let (repaired, repairs) = repair(&bytes[8..]).unwrap();
+        assert_eq!(repairs, vec![Repair::MissingSignature]);
+        assert_eq!(repaired.as_bytes(), bytes);
+
+        let mut corrupt = bytes.clone();
+        corrupt[1] = b'X';
+        let (repaired, repairs) = repair(&corrupt).unwrap();
+        assert_eq!(repairs, vec![Repair::CorruptSignature]);
+        assert_eq!(repaired.as_bytes(), bytes);
+
+        assert!(repair(b"not a PNG at all").is_err());
+    }
+
+    #[test]
+    fn test_invalid_chunk_type() {
+        let png = testing_png();
+        let mut bytes: Vec<u8> = png.header().to_vec();
+        bytes.extend(png.chunk_at(0).unwrap().as_bytes());
+        let invalid_offset = bytes.len();
+        bytes.extend(Chunk::from_text(ChunkType::from_bytes_lenient(*b"ru1t"), "Bad").as_bytes());
+        for chunk in &png.chunks()[1..] {
+            bytes.extend(chunk.as_bytes());
+        }
+
+        // the chunks after the invalid one, including IDAT, are kept
+        let (repaired, repairs) = repair(&bytes).unwrap();
+        assert_eq!(repairs, vec![
+            Repair::InvalidChunkType { offset: invalid_offset, chunk_type: "ru1t".to_string() }
+        ]);
+        assert_eq!(repaired.as_bytes(), png.as_bytes());
+    }
+
+    #[test]
+    fn test_non_empty_iend() {
+        let mut png = testing_png();
+        png.remove_chunk(IEND).unwrap();
+        png.append_chunk(Chunk::from_text(ChunkType::from_str(IEND).unwrap(), "data"));
+
+        let (repaired, repairs) = repair(&png.as_bytes()).unwrap();
+        assert_eq!(repairs, vec![Repair::NonEmptyIend(4)]);
+        assert_eq!(repaired.as_bytes(), testing_png().as_bytes());
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt::Display;
+
+use crate::Result;
+
+/// Magic bytes at the start of a tagged message.
+pub const MAGIC: &[u8; 4] = b"HKPG";
+
+/// Version of the tagged message format.
+pub const VERSION: u8 = 1;
+
+/// Number of bytes before the payload, i.e., the magic, the version and the payload length.
+const HEADER_LENGTH: usize = MAGIC.len() + 1 + 4;
+
+/// Tags the payload as a message written by this tool,
+/// i.e., prefixes it with the magic, the version byte and the 4-byte payload length.
+/// The length is stored in big-endian byte order.
+pub fn encode(payload: &[u8]) -> Result<Vec<u8>> {
+
+    let length: u32 = payload.len()
+        .try_into()
+        .map_err(|_| Box::new(TagError::PayloadTooLarge(payload.len())))?;
+
+    Ok(
+        MAGIC.iter()
+            .chain([VERSION].iter())
+            .chain(length.to_be_bytes().iter())
+            .chain(payload.iter())
+            .copied()
+            .collect()
+    )
+}
+
+/// Checks the tag of the message and returns the payload.
+pub fn decode(bytes: &[u8]) -> Result<Vec<u8>> {
+
+    if bytes.len() < HEADER_LENGTH || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(Box::new(TagError::Untagged));
+    }
+
+    let version = bytes[MAGIC.len()];
+    if version != VERSION {
+        return Err(Box::new(TagError::UnsupportedVersion(version)));
+    }
+
+    // the stored length must agree with the actual payload
+    let length = u32::from_be_bytes(bytes[MAGIC.len() + 1..HEADER_LENGTH].try_into()?) as usize;
+    let payload = &bytes[HEADER_LENGTH..];
+    if payload.len() != length {
+        return Err(Box::new(TagError::LengthMismatch { expected: length, found: payload.len() }));
+    }
+
+    Ok(payload.to_vec())
+}
+
+/// Checks if the bytes start with the tag of this tool.
+pub fn is_tagged(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+#[derive(Debug)]
+pub enum TagError {
+    /// The message does not start with the magic bytes.
+    Untagged,
+
+    /// The version byte is not supported.
+    UnsupportedVersion(u8),
+
+    /// The stored payload length is not the actual one.
+    LengthMismatch { expected: usize, found: usize },
+
+    /// The payload length does not fit in 4 bytes.
+    PayloadTooLarge(usize)
+}
+
+impl std::error::Error for TagError {}
+
+impl Display for TagError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Untagged => {
+                write!(f, "The message is not tagged by hackpng")
+            },
+            Self::UnsupportedVersion(version) => {
+                write!(f, "The tagged message has an unsupported version {}", version)
+            },
+            Self::LengthMismatch { expected, found } => {
+                write!(f, "The tagged message should have {} bytes of payload while it has {}", expected, found)
+            },
+            Self::PayloadTooLarge(length) => {
+                write!(f, "The payload of {} bytes is too large to be tagged", length)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let tagged = encode(b"Message").unwrap();
+        assert_eq!(&tagged[..5], b"HKPG\x01");
+        assert_eq!(&tagged[5..9], [0, 0, 0, 7]);
+        assert!(is_tagged(&tagged));
+        assert_eq!(decode(&tagged).unwrap(), b"Message");
+        assert_eq!(decode(&encode(b"").unwrap()).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_untagged() {
+        assert!(!is_tagged(b"Message"));
+        let result = decode(b"Message that is long enough");
+        assert_eq!(result.unwrap_err().to_string(), "The message is not tagged by hackpng");
+        assert!(decode(b"HKPG").is_err());
+    }
+
+    #[test]
+    fn test_invalid_tag() {
+        let mut tagged = encode(b"Message").unwrap();
+        tagged.pop();
+        assert!(decode(&tagged).is_err());
+
+        let mut tagged = encode(b"Message").unwrap();
+        tagged[4] = 2;
+        assert!(decode(&tagged).is_err());
+    }
+}
@@ -0,0 +1,531 @@
+use std::{
+    io::{Read, Write},
+    str::FromStr,
+    fmt::Display
+};
+
+use flate2::{
+    Compression,
+    read::ZlibDecoder,
+    write::ZlibEncoder
+};
+
+use crate::{Result, Error};
+use crate::chunk::{Chunk, latin1_string};
+use crate::chunk_type::ChunkType;
+
+/// Type code of the textual data chunk.
+pub const TEXT: &str = "tEXt";
+
+/// Type code of the compressed textual data chunk.
+pub const ZTXT: &str = "zTXt";
+
+/// Type code of the international textual data chunk.
+pub const ITXT: &str = "iTXt";
+
+/// The only compression method defined by the PNG specification (zlib deflate).
+const COMPRESSION_METHOD_DEFLATE: u8 = 0;
+
+/// Maximum number of bytes a compressed text is decompressed into,
+/// which guards against decompression bombs.
+pub const MAX_DECOMPRESSED_LENGTH: usize = 16 * 1024 * 1024;
+
+/// A `tEXt` chunk consisting of a keyword and uncompressed Latin-1 text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextChunk {
+    keyword: String,
+    text: String
+}
+
+impl TextChunk {
+
+    /// Creates a `tEXt` chunk after validating the keyword and text.
+    pub fn new(keyword: &str, text: &str) -> Result<Self> {
+
+        validate_keyword(keyword)?;
+
+        if !is_latin1(text) {
+            return Err(Box::new(TextError::InvalidText));
+        }
+
+        Ok(TextChunk {
+            keyword: keyword.to_string(),
+            text: text.to_string()
+        })
+    }
+
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Builds the chunk with the keyword, a null separator and the text.
+    pub fn to_chunk(&self) -> Result<Chunk> {
+
+        let mut data: Vec<u8> = latin1_bytes(&self.keyword);
+        data.push(0);
+        data.extend(latin1_bytes(&self.text));
+
+        Ok(Chunk::new(ChunkType::from_str(TEXT)?, data))
+    }
+
+}
+
+impl TryFrom<&Chunk> for TextChunk {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        if chunk.type_str() != Some(TEXT) {
+            return Err(Box::new(TextError::UnexpectedChunkType(chunk.chunk_type().to_string())));
+        }
+
+        // the keyword is terminated by a null separator
+        let data = chunk.data();
+        let separator_index = data.iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| Box::new(TextError::MissingSeparator))?;
+        let keyword = latin1_string(&data[..separator_index]);
+        validate_keyword(&keyword)?;
+
+        // the rest is the text
+        let text = latin1_string(&data[separator_index + 1..]);
+
+        Ok(TextChunk { keyword, text })
+    }
+
+}
+
+/// A `zTXt` chunk consisting of a keyword and compressed Latin-1 text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZtxtChunk {
+    keyword: String,
+    text: String
+}
+
+impl ZtxtChunk {
+
+    /// Creates a `zTXt` chunk after validating the keyword and text.
+    pub fn new(keyword: &str, text: &str) -> Result<Self> {
+
+        validate_keyword(keyword)?;
+
+        if !is_latin1(text) {
+            return Err(Box::new(TextError::InvalidText));
+        }
+
+        Ok(ZtxtChunk {
+            keyword: keyword.to_string(),
+            text: text.to_string()
+        })
+    }
+
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Builds the chunk with the keyword, a null separator,
+    /// the compression method and the compressed text.
+    pub fn to_chunk(&self) -> Result<Chunk> {
+
+        let mut data: Vec<u8> = latin1_bytes(&self.keyword);
+        data.push(0);
+        data.push(COMPRESSION_METHOD_DEFLATE);
+        data.extend(compress(&latin1_bytes(&self.text))?);
+
+        Ok(Chunk::new(ChunkType::from_str(ZTXT)?, data))
+    }
+
+}
+
+impl TryFrom<&Chunk> for ZtxtChunk {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        if chunk.type_str() != Some(ZTXT) {
+            return Err(Box::new(TextError::UnexpectedChunkType(chunk.chunk_type().to_string())));
+        }
+
+        // the keyword is terminated by a null separator
+        let data = chunk.data();
+        let separator_index = data.iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| Box::new(TextError::MissingSeparator))?;
+        let keyword = latin1_string(&data[..separator_index]);
+        validate_keyword(&keyword)?;
+
+        // the compression method follows the separator
+        let compression_method = *data.get(separator_index + 1)
+            .ok_or_else(|| Box::new(TextError::MissingSeparator))?;
+        if compression_method != COMPRESSION_METHOD_DEFLATE {
+            return Err(Box::new(TextError::UnsupportedCompressionMethod(compression_method)));
+        }
+
+        // the rest is the compressed text
+        let text = latin1_string(&decompress(&data[separator_index + 2..])?);
+
+        Ok(ZtxtChunk { keyword, text })
+    }
+
+}
+
+/// An `iTXt` chunk consisting of a Latin-1 keyword, a language tag,
+/// a translated keyword and UTF-8 text, which is optionally compressed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItxtChunk {
+    keyword: String,
+    compressed: bool,
+    language_tag: String,
+    translated_keyword: String,
+    text: String
+}
+
+impl ItxtChunk {
+
+    /// Creates an `iTXt` chunk after validating the keyword and language tag.
+    /// The language tag may be empty if the language is unknown.
+    pub fn new(keyword: &str, language_tag: &str, translated_keyword: &str, text: &str, compressed: bool) -> Result<Self> {
+
+        validate_keyword(keyword)?;
+
+        // the language tag consists of ASCII letters, digits and hyphens
+        if !language_tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(Box::new(TextError::InvalidLanguageTag(language_tag.to_string())));
+        }
+
+        // the null character is used as the separator
+        if translated_keyword.contains('\0') {
+            return Err(Box::new(TextError::InvalidText));
+        }
+
+        Ok(ItxtChunk {
+            keyword: keyword.to_string(),
+            compressed,
+            language_tag: language_tag.to_string(),
+            translated_keyword: translated_keyword.to_string(),
+            text: text.to_string()
+        })
+    }
+
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+
+    pub fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+
+    pub fn language_tag(&self) -> &str {
+        &self.language_tag
+    }
+
+    pub fn translated_keyword(&self) -> &str {
+        &self.translated_keyword
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Builds the chunk with the keyword, the compression flag and method,
+    /// the language tag, the translated keyword and the text,
+    /// where the variable-length fields are terminated by null separators.
+    pub fn to_chunk(&self) -> Result<Chunk> {
+
+        let mut data: Vec<u8> = latin1_bytes(&self.keyword);
+        data.push(0);
+        data.push(self.compressed as u8);
+        data.push(COMPRESSION_METHOD_DEFLATE);
+        data.extend(self.language_tag.as_bytes());
+        data.push(0);
+        data.extend(self.translated_keyword.as_bytes());
+        data.push(0);
+        if self.compressed {
+            data.extend(compress(self.text.as_bytes())?);
+        } else {
+            data.extend(self.text.as_bytes());
+        }
+
+        Ok(Chunk::new(ChunkType::from_str(ITXT)?, data))
+    }
+
+}
+
+impl TryFrom<&Chunk> for ItxtChunk {
+
+    type Error = Error;
+
+    fn try_from(chunk: &Chunk) -> std::result::Result<Self, Self::Error> {
+
+        if chunk.type_str() != Some(ITXT) {
+            return Err(Box::new(TextError::UnexpectedChunkType(chunk.chunk_type().to_string())));
+        }
+
+        // keyword
+        let (keyword, data) = split_at_separator(chunk.data())?;
+        let keyword = latin1_string(keyword);
+        validate_keyword(&keyword)?;
+
+        // compression flag and method
+        if data.len() < 2 {
+            return Err(Box::new(TextError::MissingSeparator));
+        }
+        let compressed = data[0] != 0;
+        if compressed && data[1] != COMPRESSION_METHOD_DEFLATE {
+            return Err(Box::new(TextError::UnsupportedCompressionMethod(data[1])));
+        }
+
+        // language tag and translated keyword
+        let (language_tag, data) = split_at_separator(&data[2..])?;
+        let (translated_keyword, text) = split_at_separator(data)?;
+
+        // text
+        let text = if compressed {
+            decompress(text)?
+        } else {
+            text.to_vec()
+        };
+
+        Ok(ItxtChunk {
+            keyword,
+            compressed,
+            language_tag: utf8_string(language_tag)?,
+            translated_keyword: utf8_string(translated_keyword)?,
+            text: utf8_string(&text)?
+        })
+    }
+
+}
+
+/// Splits the bytes at the first null separator, which is excluded.
+fn split_at_separator(bytes: &[u8]) -> Result<(&[u8], &[u8])> {
+    let separator_index = bytes.iter()
+        .position(|byte| *byte == 0)
+        .ok_or_else(|| Box::new(TextError::MissingSeparator))?;
+    Ok((&bytes[..separator_index], &bytes[separator_index + 1..]))
+}
+
+/// Decodes the UTF-8 bytes into a string.
+fn utf8_string(bytes: &[u8]) -> Result<String> {
+    String::from_utf8(bytes.to_vec())
+        .map_err(|_| Box::new(TextError::InvalidText).into())
+}
+
+/// Checks that the keyword has 1-79 printable Latin-1 characters
+/// without leading, trailing or consecutive spaces.
+pub fn validate_keyword(keyword: &str) -> Result<()> {
+
+    let n_chars = keyword.chars().count();
+    let is_valid = (1..=79).contains(&n_chars)
+        && keyword.chars().all(|c| matches!(c as u32, 32..=126 | 161..=255))
+        && !keyword.starts_with(' ')
+        && !keyword.ends_with(' ')
+        && !keyword.contains("  ");
+
+    if !is_valid {
+        return Err(Box::new(TextError::InvalidKeyword(keyword.to_string())));
+    }
+
+    Ok(())
+}
+
+/// Compresses the bytes into a zlib stream.
+pub fn compress(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    Ok(encoder.finish()?)
+}
+
+/// Decompresses the zlib stream into at most [`MAX_DECOMPRESSED_LENGTH`] bytes.
+pub fn decompress(bytes: &[u8]) -> Result<Vec<u8>> {
+    decompress_limited(bytes, MAX_DECOMPRESSED_LENGTH)
+}
+
+/// Decompresses the zlib stream, failing once the output exceeds `limit` bytes.
+pub fn decompress_limited(bytes: &[u8], limit: usize) -> Result<Vec<u8>> {
+
+    // read one byte more than the limit to tell if the output exceeds it
+    let mut decompressed: Vec<u8> = vec![];
+    ZlibDecoder::new(bytes)
+        .take((limit as u64).saturating_add(1))
+        .read_to_end(&mut decompressed)?;
+
+    if decompressed.len() > limit {
+        return Err(Box::new(TextError::DecompressedTooLong(limit)));
+    }
+
+    Ok(decompressed)
+}
+
+/// Checks if every character of the string is in Latin-1.
+fn is_latin1(s: &str) -> bool {
+    s.chars().all(|c| (c as u32) <= 0xff)
+}
+
+/// Encodes the string in Latin-1, assuming every character is in Latin-1.
+fn latin1_bytes(s: &str) -> Vec<u8> {
+    s.chars().map(|c| c as u8).collect()
+}
+
+#[derive(Debug)]
+pub enum TextError {
+    /// The chunk is not of the expected text chunk type.
+    UnexpectedChunkType(String),
+
+    /// The null separator after the keyword is missing.
+    MissingSeparator,
+
+    /// The keyword violates the constraints of the PNG specification.
+    InvalidKeyword(String),
+
+    /// The text contains characters which cannot be encoded.
+    InvalidText,
+
+    /// Only the zlib deflate compression method (0) is supported.
+    UnsupportedCompressionMethod(u8),
+
+    /// The language tag contains characters other than ASCII letters, digits and hyphens.
+    InvalidLanguageTag(String),
+
+    /// The compressed text decompresses into more than the contained number of bytes.
+    DecompressedTooLong(usize)
+}
+
+impl std::error::Error for TextError {}
+
+impl Display for TextError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedChunkType(chunk_type) => {
+                write!(f, "Expected a text chunk but got {}", chunk_type)
+            },
+            Self::MissingSeparator => {
+                write!(f, "The null separator after the keyword is missing")
+            },
+            Self::InvalidKeyword(keyword) => {
+                write!(f, "The keyword {:?} is invalid", keyword)
+            },
+            Self::InvalidText => {
+                write!(f, "The text contains characters which cannot be encoded")
+            },
+            Self::UnsupportedCompressionMethod(method) => {
+                write!(f, "The compression method {} is not supported", method)
+            },
+            Self::InvalidLanguageTag(language_tag) => {
+                write!(f, "The language tag {:?} is invalid", language_tag)
+            },
+            Self::DecompressedTooLong(limit) => {
+                write!(f, "The compressed text decompresses into more than {} bytes", limit)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_round_trip() {
+        let text = TextChunk::new("Title", "Café").unwrap();
+        let chunk = text.to_chunk().unwrap();
+        assert_eq!(&chunk.chunk_type().to_string(), "tEXt");
+        assert_eq!(chunk.data(), b"Title\0Caf\xe9");
+
+        let parsed = TextChunk::try_from(&chunk).unwrap();
+        assert_eq!(parsed, text);
+        assert!(TextChunk::new("", "text").is_err());
+    }
+
+    #[test]
+    fn test_ztxt_round_trip() {
+        let ztxt = ZtxtChunk::new("Comment", "Café au lait, café au lait, café au lait").unwrap();
+        let chunk = ztxt.to_chunk().unwrap();
+        assert_eq!(&chunk.chunk_type().to_string(), "zTXt");
+        assert!(chunk.data().starts_with(b"Comment\0\0"));
+
+        let parsed = ZtxtChunk::try_from(&chunk).unwrap();
+        assert_eq!(parsed, ztxt);
+        assert_eq!(parsed.keyword(), "Comment");
+        assert_eq!(parsed.text(), "Café au lait, café au lait, café au lait");
+    }
+
+    #[test]
+    fn test_invalid_ztxt() {
+        assert!(ZtxtChunk::new(" Comment", "text").is_err());
+        assert!(ZtxtChunk::new("Comment", "文字").is_err());
+
+        let chunk = Chunk::new(ChunkType::from_str(ZTXT).unwrap(), b"Comment\0\x01abc".to_vec());
+        assert!(ZtxtChunk::try_from(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_uncompressed_itxt_round_trip() {
+        let itxt = ItxtChunk::new("Title", "ja", "タイトル", "こんにちは", false).unwrap();
+        let chunk = itxt.to_chunk().unwrap();
+        assert_eq!(&chunk.chunk_type().to_string(), "iTXt");
+        assert!(chunk.data().ends_with("こんにちは".as_bytes()));
+
+        let parsed = ItxtChunk::try_from(&chunk).unwrap();
+        assert_eq!(parsed, itxt);
+        assert_eq!(parsed.language_tag(), "ja");
+        assert_eq!(parsed.translated_keyword(), "タイトル");
+        assert_eq!(parsed.text(), "こんにちは");
+    }
+
+    #[test]
+    fn test_decompress_limited() {
+        let compressed = compress(&[b'a'; 100]).unwrap();
+        assert_eq!(decompress_limited(&compressed, 100).unwrap(), vec![b'a'; 100]);
+        assert!(decompress_limited(&compressed, 99).is_err());
+    }
+
+    #[test]
+    fn test_oversized_compressed_itxt() {
+        let text = "a".repeat(MAX_DECOMPRESSED_LENGTH + 1);
+        let chunk = ItxtChunk::new("Bomb", "", "", &text, true).unwrap().to_chunk().unwrap();
+        assert!(chunk.data_len() < 1024 * 1024);
+
+        let error = ItxtChunk::try_from(&chunk).unwrap_err();
+        assert_eq!(error.to_string(), TextError::DecompressedTooLong(MAX_DECOMPRESSED_LENGTH).to_string());
+    }
+
+    #[test]
+    fn test_compressed_itxt_round_trip() {
+        let text = "Grüße! ".repeat(20);
+        let itxt = ItxtChunk::new("Description", "de-DE", "Beschreibung", &text, true).unwrap();
+        let chunk = itxt.to_chunk().unwrap();
+        assert!(chunk.data_len() < text.len());
+
+        let parsed = ItxtChunk::try_from(&chunk).unwrap();
+        assert!(parsed.is_compressed());
+        assert_eq!(parsed.text(), text);
+    }
+
+    #[test]
+    fn test_invalid_itxt() {
+        assert!(ItxtChunk::new("Title", "en us", "", "text", false).is_err());
+
+        let chunk = Chunk::new(ChunkType::from_str(ITXT).unwrap(), b"Title\0\0\0en".to_vec());
+        assert!(ItxtChunk::try_from(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_validate_keyword() {
+        assert!(validate_keyword("Title").is_ok());
+        assert!(validate_keyword("").is_err());
+        assert!(validate_keyword("Two  spaces").is_err());
+        assert!(validate_keyword(&"k".repeat(80)).is_err());
+    }
+}
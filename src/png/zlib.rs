@@ -0,0 +1,203 @@
+use std::{fmt::Display, io::Write};
+
+use flate2::read::DeflateDecoder;
+
+use crate::Result;
+
+/// Maximum number of bytes in a stored (uncompressed) deflate block.
+const MAX_STORED_BLOCK_SIZE: usize = 65535;
+
+/// Computes the Adler-32 checksum of the bytes,
+/// which is stored at the end of a zlib stream.
+pub fn adler32(bytes: &[u8]) -> u32 {
+    let mut hasher = Adler32::new();
+    hasher.update(bytes);
+    hasher.checksum()
+}
+
+/// Computes the Adler-32 checksum incrementally,
+/// e.g., of data written into it as an `io::Write` sink without keeping the data.
+#[derive(Debug, Clone, Copy)]
+pub struct Adler32 {
+    a: u32,
+    b: u32
+}
+
+impl Adler32 {
+
+    const MOD_ADLER: u32 = 65521;
+
+    pub fn new() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+
+    /// Feeds more bytes into the checksum.
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.a = (self.a + *byte as u32) % Self::MOD_ADLER;
+            self.b = (self.b + self.a) % Self::MOD_ADLER;
+        }
+    }
+
+    /// The checksum of all bytes fed so far.
+    pub fn checksum(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for Adler32 {
+
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+}
+
+/// Wraps the bytes into a zlib stream without compression,
+/// i.e., using only stored deflate blocks.
+pub fn compress_stored(bytes: &[u8]) -> Vec<u8> {
+
+    // zlib header: deflate with a 32K window and no preset dictionary
+    let mut stream: Vec<u8> = vec![0x78, 0x01];
+
+    // stored blocks, where an empty input still needs one final block
+    let blocks: Vec<&[u8]> = if bytes.is_empty() {
+        vec![bytes]
+    } else {
+        bytes.chunks(MAX_STORED_BLOCK_SIZE).collect()
+    };
+    for (index, block) in blocks.iter().enumerate() {
+        let is_final = index == blocks.len() - 1;
+        let length = block.len() as u16;
+        stream.push(is_final as u8);
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend(*block);
+    }
+
+    // checksum of the uncompressed bytes
+    stream.extend(adler32(bytes).to_be_bytes());
+
+    stream
+}
+
+/// Number of bytes of the zlib stream produced by [`compress_stored`] for `n_bytes` bytes of input,
+/// or `None` if it overflows.
+pub fn stored_len(n_bytes: usize) -> Option<usize> {
+    let n_blocks = n_bytes.div_ceil(MAX_STORED_BLOCK_SIZE).max(1);
+    n_blocks.checked_mul(5)?.checked_add(n_bytes)?.checked_add(6)
+}
+
+/// Checks the Adler-32 checksum at the end of the zlib stream
+/// against the checksum of the decompressed data.
+pub fn verify_adler32(stream: &[u8]) -> Result<()> {
+
+    // the stream has a 2-byte header and a 4-byte checksum
+    if stream.len() < 6 {
+        return Err(Box::new(ZlibError::TooShort(stream.len())));
+    }
+
+    // checksum the deflate data between the header and the checksum while decompressing,
+    // so that the decompressed data is never kept in memory
+    let mut hasher = Adler32::new();
+    std::io::copy(&mut DeflateDecoder::new(&stream[2..stream.len() - 4]), &mut hasher)?;
+
+    let expected = u32::from_be_bytes(stream[stream.len() - 4..].try_into()?);
+    let found = hasher.checksum();
+    if found != expected {
+        return Err(Box::new(ZlibError::Adler32Mismatch { expected, found }));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum ZlibError {
+    /// The stream has the contained number of bytes, which is too few.
+    TooShort(usize),
+
+    /// The Adler-32 checksum stored in the stream is not the one of the decompressed data.
+    Adler32Mismatch { expected: u32, found: u32 }
+}
+
+impl std::error::Error for ZlibError {}
+
+impl Display for ZlibError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooShort(length) => {
+                write!(f, "The zlib stream has only {} bytes", length)
+            },
+            Self::Adler32Mismatch { expected, found } => {
+                write!(f, "The Adler-32 checksum of the zlib stream is {:08x} but the data gives {:08x}", expected, found)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adler32() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+
+        // the checksum is the same when computed incrementally
+        let mut hasher = Adler32::new();
+        hasher.write_all(b"Wiki").unwrap();
+        hasher.write_all(b"pedia").unwrap();
+        assert_eq!(hasher.checksum(), 0x11e60398);
+    }
+
+    #[test]
+    fn test_verify_adler32() {
+        // "Wikipedia" compressed by zlib, whose checksum is 0x11e60398
+        let stream = [
+            0x78, 0x9c, 0x0b, 0xcf, 0xcc, 0xce, 0x2c, 0x48, 0x4d, 0xc9, 0x4c, 0x04, 0x00,
+            0x11, 0xe6, 0x03, 0x98
+        ];
+        verify_adler32(&stream).unwrap();
+        verify_adler32(&compress_stored(b"abc")).unwrap();
+
+        let mut corrupt = stream;
+        corrupt[16] = 0x99;
+        assert!(verify_adler32(&corrupt).unwrap_err().to_string().contains("11e60399"));
+        assert!(verify_adler32(&stream[..5]).is_err());
+    }
+
+    #[test]
+    fn test_compress_stored() {
+        let stream = compress_stored(b"abc");
+        assert_eq!(stream, vec![
+            0x78, 0x01,
+            1, 3, 0, 0xfc, 0xff, b'a', b'b', b'c',
+            0x02, 0x4d, 0x01, 0x27
+        ]);
+    }
+
+    #[test]
+    fn test_compress_stored_multiple_blocks() {
+        let bytes = vec![0; MAX_STORED_BLOCK_SIZE + 1];
+        let stream = compress_stored(&bytes);
+        assert_eq!(stream.len(), 2 + 2 * 5 + bytes.len() + 4);
+        assert_eq!(stored_len(bytes.len()), Some(stream.len()));
+        assert_eq!(stored_len(0), Some(compress_stored(b"").len()));
+        assert_eq!(stream[2], 0);
+        assert_eq!(stream[2 + 5 + MAX_STORED_BLOCK_SIZE], 1);
+    }
+}
@@ -0,0 +1,348 @@
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+/// Path of the fixture file under `tests/fixtures`.
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+/// Path of a scratch file in the temporary directory, removing any leftover.
+fn scratch_file(name: &str) -> PathBuf {
+    let filepath = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_file(&filepath);
+    filepath
+}
+
+fn hackpng() -> Command {
+    Command::cargo_bin("hackpng").unwrap()
+}
+
+#[test]
+fn test_decode_fixture() {
+    hackpng()
+        .args(["decode", fixture("message.png").to_str().unwrap(), "ruSt"])
+        .assert()
+        .success()
+        .stdout("Hello, fixture!\n");
+}
+
+#[test]
+fn test_encode_decode_round_trip() {
+    let output_filepath = scratch_file("hackpng_it_round_trip.png");
+
+    hackpng()
+        .args([
+            "encode", fixture("message.png").to_str().unwrap(), "teSt",
+            "-m", "Round trip", "-o", output_filepath.to_str().unwrap()
+        ])
+        .assert()
+        .success();
+
+    hackpng()
+        .args(["decode", output_filepath.to_str().unwrap(), "teSt"])
+        .assert()
+        .success()
+        .stdout("Round trip\n");
+
+    // the original message is kept
+    hackpng()
+        .args(["decode", output_filepath.to_str().unwrap(), "ruSt"])
+        .assert()
+        .success()
+        .stdout("Hello, fixture!\n");
+
+    std::fs::remove_file(output_filepath).unwrap();
+}
+
+#[test]
+fn test_encode_new_png_round_trip() {
+    let filepath = scratch_file("hackpng_it_new_png.png");
+
+    hackpng()
+        .args(["encode", filepath.to_str().unwrap(), "ruSt", "-m", "deadbeef", "--hex", "--new", "2x2"])
+        .assert()
+        .success();
+
+    hackpng()
+        .args(["decode", filepath.to_str().unwrap(), "ruSt", "--hex"])
+        .assert()
+        .success()
+        .stdout("deadbeef\n");
+
+    std::fs::remove_file(filepath).unwrap();
+}
+
+#[test]
+fn test_message_not_found() {
+    hackpng()
+        .args(["decode", fixture("message.png").to_str().unwrap(), "teSt"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("No message of type teSt is found"));
+}
+
+#[test]
+fn test_chunk_not_found() {
+    let output_filepath = scratch_file("hackpng_it_chunk_not_found.png");
+
+    hackpng()
+        .args([
+            "retype", fixture("message.png").to_str().unwrap(), "teSt", "fiNd",
+            "-o", output_filepath.to_str().unwrap()
+        ])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("No chunk of type teSt is found"));
+
+    assert!(!output_filepath.exists());
+}
+
+#[test]
+fn test_not_a_png() {
+    hackpng()
+        .args(["info", fixture("not_a_png.txt").to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("PNG header"));
+}
+
+#[test]
+fn test_corrupt_chunk() {
+    hackpng()
+        .args(["decode", fixture("corrupt.png").to_str().unwrap(), "ruSt"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("The CRC of the message chunk 1 does not match"));
+
+    hackpng()
+        .args(["decode", fixture("corrupt.png").to_str().unwrap(), "ruSt", "--strict"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("byte offset 33"));
+
+    // info still reports the file and points out the CRC
+    hackpng()
+        .args(["info", fixture("corrupt.png").to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("the CRC of chunk 1 at byte offset 56 does not match"));
+}
+
+#[test]
+fn test_invalid_chunk_type() {
+    hackpng()
+        .args(["decode", fixture("message.png").to_str().unwrap(), "ru1t"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_missing_file() {
+    hackpng()
+        .args(["info", fixture("missing.png").to_str().unwrap()])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn test_invalid_arguments() {
+    hackpng()
+        .args(["encode", fixture("message.png").to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn test_list_by_length() {
+    // the fixture has chunks of 13, 31, 0 and 15 data bytes
+    hackpng()
+        .args([
+            "list", fixture("message.png").to_str().unwrap(),
+            "--min-length", "14", "--max-length", "20", "--output-format", "csv"
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3,ruSt,15,"))
+        .stdout(predicate::str::contains("IDAT").not());
+}
+
+#[test]
+fn test_validate_non_empty_iend() {
+    use std::str::FromStr;
+    use hackpng::{chunk::Chunk, chunk_type::ChunkType, png::Png};
+
+    let mut png = Png::new_blank(1, 1).unwrap();
+    png.remove_chunk("IEND").unwrap();
+    png.append_chunk(Chunk::from_text(ChunkType::from_str("IEND").unwrap(), "data"));
+    let filepath = scratch_file("hackpng_it_non_empty_iend.png");
+    std::fs::write(&filepath, png.as_bytes()).unwrap();
+
+    hackpng()
+        .args(["validate", filepath.to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("The IEND chunk must be empty but it has 4 bytes"));
+
+    hackpng()
+        .args(["info", filepath.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("IDAT chunks: 1"))
+        .stdout(predicate::str::contains("The IEND chunk must be empty"));
+
+    std::fs::remove_file(filepath).unwrap();
+}
+
+#[test]
+fn test_decode_json() {
+    let output = hackpng()
+        .args(["decode", fixture("message.png").to_str().unwrap(), "ruSt", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report["found"], true);
+    assert_eq!(report["type"], "ruSt");
+    assert_eq!(report["data_base64"], "SGVsbG8sIGZpeHR1cmUh");
+
+    let output = hackpng()
+        .args(["decode", fixture("message.png").to_str().unwrap(), "teSt", "--json"])
+        .assert()
+        .code(1)
+        .get_output()
+        .stdout
+        .clone();
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report, serde_json::json!({"found": false}));
+}
+
+#[test]
+fn test_decode_latin1() {
+    use std::str::FromStr;
+    use hackpng::{chunk::Chunk, chunk_type::ChunkType, png::Png};
+
+    let mut png = Png::new_blank(1, 1).unwrap();
+    png.insert_chunk(1, Chunk::new(ChunkType::from_str("tEXt").unwrap(), b"Caf\xe9".to_vec())).unwrap();
+    let filepath = scratch_file("hackpng_it_latin1.png");
+    std::fs::write(&filepath, png.as_bytes()).unwrap();
+
+    hackpng()
+        .args(["decode", filepath.to_str().unwrap(), "tEXt"])
+        .assert()
+        .code(2);
+
+    hackpng()
+        .args(["decode", filepath.to_str().unwrap(), "tEXt", "--encoding", "latin1"])
+        .assert()
+        .success()
+        .stdout("Café\n");
+
+    std::fs::remove_file(filepath).unwrap();
+}
+
+#[test]
+fn test_info_interlaced() {
+    hackpng()
+        .args(["info", fixture("interlaced.png").to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Interlaced: Adam7"));
+
+    hackpng()
+        .args(["info", fixture("message.png").to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Interlaced: no"));
+}
+
+#[test]
+fn test_chunk_type_suggestion() {
+    hackpng()
+        .args(["decode", fixture("message.png").to_str().unwrap(), "IDTA"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("No message of type IDTA is found, did you mean IDAT?"));
+}
+
+#[test]
+fn test_hunt_fixture() {
+    hackpng()
+        .args(["hunt", fixture("message.png").to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ruSt"))
+        .stdout(predicate::str::contains("Hello, fixture!"));
+}
+
+#[test]
+fn test_info_resolution() {
+    use std::str::FromStr;
+    use hackpng::{chunk::Chunk, chunk_type::ChunkType, png::Png};
+
+    // 11811 pixels per meter is 300 DPI
+    let mut png = Png::new_blank(1, 1).unwrap();
+    let data = [&11811u32.to_be_bytes()[..], &11811u32.to_be_bytes()[..], &[1]].concat();
+    png.insert_chunk(1, Chunk::new(ChunkType::from_str("pHYs").unwrap(), data)).unwrap();
+    let filepath = scratch_file("hackpng_it_resolution.png");
+    std::fs::write(&filepath, png.as_bytes()).unwrap();
+
+    hackpng()
+        .args(["info", filepath.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Resolution: 300 x 300 DPI"));
+
+    std::fs::remove_file(filepath).unwrap();
+}
+
+#[test]
+fn test_validate_non_utf8_chunk_type() {
+    use hackpng::{chunk::Chunk, chunk_type::ChunkType, png::Png};
+
+    let mut png = Png::new_blank(1, 1).unwrap();
+    png.insert_chunk(1, Chunk::from_text(ChunkType::from_bytes_lenient([b'R', 0xff, b'S', b't']), "Message")).unwrap();
+    let filepath = scratch_file("hackpng_it_non_utf8_type.png");
+    std::fs::write(&filepath, png.as_bytes()).unwrap();
+
+    // the invalid chunk type is reported instead of crashing
+    hackpng()
+        .args(["validate", filepath.to_str().unwrap()])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("Chunk 1 ([82, 255, 83, 116]) is invalid"));
+
+    std::fs::remove_file(filepath).unwrap();
+}
+
+#[test]
+fn test_compressed_text_bomb() {
+    use hackpng::png::{Png, text::{ItxtChunk, MAX_DECOMPRESSED_LENGTH}};
+
+    let text = "a".repeat(MAX_DECOMPRESSED_LENGTH + 1);
+    let mut png = Png::new_blank(1, 1).unwrap();
+    png.insert_chunk(1, ItxtChunk::new("Bomb", "", "", &text, true).unwrap().to_chunk().unwrap()).unwrap();
+    let filepath = scratch_file("hackpng_it_text_bomb.png");
+    std::fs::write(&filepath, png.as_bytes()).unwrap();
+
+    hackpng()
+        .args(["decode", filepath.to_str().unwrap(), "iTXt"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("decompresses into more than"));
+
+    hackpng()
+        .args(["info", filepath.to_str().unwrap()])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("decompresses into more than"));
+
+    std::fs::remove_file(filepath).unwrap();
+}